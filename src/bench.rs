@@ -0,0 +1,369 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc_counter,
+    parsing::{ParsePolicy, ParseReport, ParseWarning},
+    solution::{DynSolution, Part, SolutionError},
+    value::Value,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl TryFrom<&str> for Stage {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Solve(Part::One)),
+            "part2" => Ok(Stage::Solve(Part::Two)),
+            other => Err(format!("Unknown stage '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let len = samples.len();
+        // nearest rank, so a handful of samples still gives a real sample back
+        let p95_index = (len * 95).div_ceil(100) - 1;
+        Some(Self {
+            min: samples[0],
+            median: samples[len / 2],
+            p95: samples[p95_index],
+        })
+    }
+}
+
+// averaged over every iteration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug)]
+pub struct StageTiming {
+    pub stage: Stage,
+    pub stats: Stats,
+    pub allocations: Allocations,
+}
+
+#[derive(Debug)]
+pub struct DayBench {
+    pub day: u8,
+    pub stages: Vec<StageTiming>,
+}
+
+fn nanos(duration: Duration) -> Value {
+    Value::Int(duration.as_nanos() as i64)
+}
+
+impl From<&StageTiming> for Value {
+    fn from(value: &StageTiming) -> Self {
+        Value::from_entries(vec![
+            ("stage", Value::Str(value.stage.to_string())),
+            ("min_ns", nanos(value.stats.min)),
+            ("median_ns", nanos(value.stats.median)),
+            ("p95_ns", nanos(value.stats.p95)),
+            ("allocations", Value::Int(value.allocations.count as i64)),
+            (
+                "allocated_bytes",
+                Value::Int(value.allocations.bytes as i64),
+            ),
+        ])
+    }
+}
+
+impl From<&DayBench> for Value {
+    fn from(value: &DayBench) -> Self {
+        Value::from_entries(vec![
+            ("day", Value::Int(value.day as i64)),
+            (
+                "stages",
+                Value::List(value.stages.iter().map(Value::from).collect()),
+            ),
+        ])
+    }
+}
+
+impl From<&Slowdown> for Value {
+    fn from(value: &Slowdown) -> Self {
+        Value::from_entries(vec![
+            ("day", Value::Int(value.day as i64)),
+            ("stage", Value::Str(value.stage.to_string())),
+            ("baseline_ns", nanos(value.baseline)),
+            ("current_ns", nanos(value.current)),
+            ("percent", Value::Float(value.percent())),
+        ])
+    }
+}
+
+fn measure<T>(stage: Stage, iterations: usize, mut run: impl FnMut() -> T) -> StageTiming {
+    // one untimed run first so the first sample isn't paying for cold caches
+    black_box(run());
+
+    let mut samples = Vec::with_capacity(iterations);
+    let (start_count, start_bytes) = alloc_counter::snapshot();
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(run());
+        samples.push(start.elapsed());
+    }
+    let (end_count, end_bytes) = alloc_counter::snapshot();
+
+    // the samples vec was sized up front, so it doesn't show up in the counts
+    let iterations = iterations.max(1) as u64;
+    StageTiming {
+        stage,
+        stats: Stats::from_samples(samples).expect("Should have at least one sample"),
+        allocations: Allocations {
+            count: (end_count - start_count) / iterations,
+            bytes: (end_bytes - start_bytes) / iterations,
+        },
+    }
+}
+
+// times parsing and each solved part separately, skipping parts that aren't solved yet
+pub fn bench(
+    solution: &dyn DynSolution,
+    input: &str,
+    policy: ParsePolicy,
+    iterations: usize,
+) -> Result<DayBench, SolutionError> {
+    let iterations = iterations.max(1);
+    let parsed = solution.parse(input, &mut ParseReport::new(policy))?;
+
+    let mut stages = vec![measure(Stage::Parse, iterations, || {
+        solution.parse(input, &mut ParseReport::new(policy))
+    })];
+    for part in Part::ALL {
+        match solution.solve(parsed.as_ref(), part) {
+            Err(SolutionError::Unsolved { .. }) => continue,
+            Err(error) => return Err(error),
+            Ok(_) => stages.push(measure(Stage::Solve(part), iterations, || {
+                solution.solve(parsed.as_ref(), part)
+            })),
+        }
+    }
+    Ok(DayBench {
+        day: solution.day(),
+        stages,
+    })
+}
+
+// a saved median, one per line as '<day> <stage> <nanoseconds>'
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineEntry {
+    pub day: u8,
+    pub stage: Stage,
+    pub median: Duration,
+}
+
+fn parse_baseline_entry(line: &str) -> Result<BaselineEntry, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [day, stage, median] = fields[..] else {
+        return Err(format!(
+            "Expected '<day> <stage> <nanoseconds>', got '{}'",
+            line
+        ));
+    };
+    Ok(BaselineEntry {
+        day: day
+            .parse()
+            .map_err(|error| format!("Invalid day '{}': {}", day, error))?,
+        stage: Stage::try_from(stage)?,
+        median: Duration::from_nanos(
+            median
+                .parse()
+                .map_err(|error| format!("Invalid nanoseconds '{}': {}", median, error))?,
+        ),
+    })
+}
+
+pub fn parse_baseline(input: &str) -> Result<Vec<BaselineEntry>, ParseWarning> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            parse_baseline_entry(line).map_err(|error| ParseWarning::new(index + 1, error))
+        })
+        .collect()
+}
+
+pub fn format_baseline(benches: &[DayBench]) -> String {
+    let mut output = String::from("# <day> <stage> <median nanoseconds>\n");
+    for bench in benches {
+        for timing in &bench.stages {
+            output += &format!(
+                "{} {} {}\n",
+                bench.day,
+                timing.stage,
+                timing.stats.median.as_nanos()
+            );
+        }
+    }
+    output
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Slowdown {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Slowdown {
+    // how much slower as a percentage, 50.0 means it took one and a half times as long
+    pub fn percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+// flags any stage whose median got more than threshold percent slower
+pub fn compare(benches: &[DayBench], baseline: &[BaselineEntry], threshold: f64) -> Vec<Slowdown> {
+    benches
+        .iter()
+        .flat_map(|bench| {
+            bench.stages.iter().filter_map(|timing| {
+                let entry = baseline
+                    .iter()
+                    .find(|entry| entry.day == bench.day && entry.stage == timing.stage)?;
+                let slowdown = Slowdown {
+                    day: bench.day,
+                    stage: timing.stage,
+                    baseline: entry.median,
+                    current: timing.stats.median,
+                };
+                (!entry.median.is_zero() && slowdown.percent() > threshold).then_some(slowdown)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{
+        Allocations, BaselineEntry, DayBench, Stage, StageTiming, Stats, bench, compare,
+        format_baseline, parse_baseline,
+    };
+    use crate::{parsing::ParsePolicy, registry, solution::Part};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn day_with_median(day: u8, median: Duration) -> DayBench {
+        DayBench {
+            day,
+            stages: vec![StageTiming {
+                stage: Stage::Parse,
+                stats: Stats {
+                    min: median,
+                    median,
+                    p95: median,
+                },
+                allocations: Allocations { count: 0, bytes: 0 },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(vec![]), None);
+        assert_eq!(
+            Stats::from_samples(vec![ms(3)]),
+            Some(Stats {
+                min: ms(3),
+                median: ms(3),
+                p95: ms(3)
+            })
+        );
+        let samples = (1..=100).rev().map(ms).collect();
+        assert_eq!(
+            Stats::from_samples(samples),
+            Some(Stats {
+                min: ms(1),
+                median: ms(51),
+                p95: ms(95)
+            })
+        );
+    }
+
+    #[test]
+    fn test_bench_example() {
+        let solution = registry::find(6).unwrap();
+        let result = bench(solution, solution.example(), ParsePolicy::Strict, 3)
+            .expect("Should bench the example");
+        assert_eq!(result.day, 6);
+        // day 6 part 2 isn't solved, so it isn't timed
+        let stages: Vec<Stage> = result.stages.iter().map(|timing| timing.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Solve(Part::One)]);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let benches = vec![day_with_median(1, ms(2)), day_with_median(4, ms(5))];
+        let baseline = parse_baseline(&format_baseline(&benches)).expect("Should parse correctly");
+        assert_eq!(
+            baseline,
+            vec![
+                BaselineEntry {
+                    day: 1,
+                    stage: Stage::Parse,
+                    median: ms(2)
+                },
+                BaselineEntry {
+                    day: 4,
+                    stage: Stage::Parse,
+                    median: ms(5)
+                },
+            ]
+        );
+        assert_eq!(parse_baseline("1 parse\n").unwrap_err().line, 1);
+        assert!(parse_baseline("1 part3 100").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = parse_baseline("1 parse 1000000\n4 parse 1000000").unwrap();
+        let benches = vec![day_with_median(1, ms(2)), day_with_median(4, ms(1))];
+        let slowdowns = compare(&benches, &baseline, 10.0);
+        assert_eq!(slowdowns.len(), 1);
+        assert_eq!(slowdowns[0].day, 1);
+        assert_eq!(slowdowns[0].percent(), 100.0);
+        assert!(compare(&benches, &baseline, 150.0).is_empty());
+    }
+}
@@ -0,0 +1,638 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::{
+    input::{INPUT_DIR_VAR, InputSource},
+    parsing::ParsePolicy,
+    solution::Part,
+    verify::ANSWERS_FILE_PATH,
+};
+
+pub const USAGE: &str = "Usage:
+  aoc_2025 run <day> [--part 1|2] [--input <file>|- | --input-dir <dir>] [--strict]
+  aoc_2025 run --all [--part 1|2] [--input-dir <dir>] [--strict] [--jobs <n>]
+  aoc_2025 verify [--answers <file>] [--input-dir <dir>]
+  aoc_2025 bench <day>|--all [--iterations <n>] [--input ...] [--strict]
+                 [--baseline <file>] [--save-baseline <file>] [--threshold <percent>]
+  aoc_2025 new <day>
+  aoc_2025 help
+
+run, verify and bench all take --format text|json, json prints a single document.
+
+Inputs default to $AOC_INPUT_DIR/day_<N>.txt when that is set,
+otherwise to the input file next to each day's source.
+
+Lines that fail to parse are skipped and reported unless --strict is given.
+run --all runs up to --jobs days at once (default 1) and prints a summary table.
+verify checks every solution against the known answers in ./answers.txt.
+bench times parsing and each part, flagging stages more than --threshold
+percent (default 10) slower than the --baseline file.
+new writes a skeleton module for the day and registers it.";
+
+// how results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            other => Err(other.into()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Target {
+    Day(u8),
+    All,
+}
+
+pub const DEFAULT_JOBS: usize = 1;
+pub const DEFAULT_ITERATIONS: usize = 100;
+// percent slower than the baseline before a stage is flagged
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub target: Target,
+    pub part: Option<Part>,
+    pub input: InputSource,
+    pub policy: ParsePolicy,
+    pub format: Format,
+    // how many days can run at the same time
+    pub jobs: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub target: Target,
+    pub input: InputSource,
+    pub policy: ParsePolicy,
+    pub iterations: usize,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    pub threshold: f64,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify {
+        answers: PathBuf,
+        input: InputSource,
+        format: Format,
+    },
+    Bench(BenchOptions),
+    New {
+        day: u8,
+    },
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingTarget,
+    MissingDay,
+    InvalidDay(String),
+    MissingValue(String),
+    InvalidPart(String),
+    UnexpectedArgument(String),
+    InputWithAll,
+    InvalidNumber { flag: String, value: String },
+    InvalidFormat(String),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "No command given"),
+            CliError::UnknownCommand(command) => write!(f, "Unknown command '{}'", command),
+            CliError::MissingTarget => write!(f, "Expected a day number or --all"),
+            CliError::MissingDay => write!(f, "Expected a day number"),
+            CliError::InvalidDay(day) => write!(f, "Invalid day '{}'", day),
+            CliError::MissingValue(flag) => write!(f, "Missing value for '{}'", flag),
+            CliError::InvalidPart(part) => write!(f, "Invalid part '{}', expected 1 or 2", part),
+            CliError::UnexpectedArgument(argument) => {
+                write!(f, "Unexpected argument '{}'", argument)
+            }
+            CliError::InputWithAll => write!(
+                f,
+                "--input can only be used with a single day, use --input-dir or ${} for --all",
+                INPUT_DIR_VAR
+            ),
+            CliError::InvalidNumber { flag, value } => {
+                write!(f, "Invalid number '{}' for '{}'", value, flag)
+            }
+            CliError::InvalidFormat(format) => {
+                write!(f, "Invalid format '{}', expected text or json", format)
+            }
+        }
+    }
+}
+
+fn next_format(flag: String, args: &mut impl Iterator<Item = String>) -> Result<Format, CliError> {
+    let value = args.next().ok_or(CliError::MissingValue(flag))?;
+    Format::try_from(value.as_str()).map_err(CliError::InvalidFormat)
+}
+
+// the flags run and bench share, for picking days, what to feed them and how to report
+struct Selection {
+    target: Option<Target>,
+    input: InputSource,
+    policy: ParsePolicy,
+    format: Format,
+}
+
+impl Selection {
+    fn new() -> Self {
+        Self {
+            target: None,
+            input: InputSource::Default,
+            policy: ParsePolicy::default(),
+            format: Format::default(),
+        }
+    }
+
+    // returns false when the argument wasn't one of ours
+    fn accept(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, CliError> {
+        match arg {
+            "--all" if self.target.is_none() => self.target = Some(Target::All),
+            "--input" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.into()))?;
+                self.input = InputSource::from(value.as_str());
+            }
+            "--input-dir" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.into()))?;
+                self.input = InputSource::Dir(PathBuf::from(value));
+            }
+            "--strict" => self.policy = ParsePolicy::Strict,
+            "--lenient" => self.policy = ParsePolicy::Lenient,
+            "--format" => self.format = next_format(arg.into(), args)?,
+            day if self.target.is_none() && !day.starts_with('-') => {
+                let day = day
+                    .parse::<u8>()
+                    .map_err(|_| CliError::InvalidDay(arg.into()))?;
+                self.target = Some(Target::Day(day));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn finish(self) -> Result<(Target, InputSource, ParsePolicy, Format), CliError> {
+        let target = self.target.ok_or(CliError::MissingTarget)?;
+        // a single file can't be the input for every day
+        if target == Target::All && matches!(self.input, InputSource::Path(_) | InputSource::Stdin)
+        {
+            return Err(CliError::InputWithAll);
+        }
+        Ok((target, self.input, self.policy, self.format))
+    }
+}
+
+fn next_number<T: std::str::FromStr>(
+    flag: String,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<T, CliError> {
+    let value = args.next().ok_or(CliError::MissingValue(flag.clone()))?;
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidNumber { flag, value })
+}
+
+fn parse_run(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut selection = Selection::new();
+    let mut part = None;
+    let mut jobs = DEFAULT_JOBS;
+    while let Some(arg) = args.next() {
+        if selection.accept(&arg, args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                part = Some(Part::try_from(value.as_str()).map_err(CliError::InvalidPart)?);
+            }
+            "--jobs" => {
+                jobs = next_number(arg.clone(), args)?;
+                if jobs == 0 {
+                    return Err(CliError::InvalidNumber {
+                        flag: arg,
+                        value: jobs.to_string(),
+                    });
+                }
+            }
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    let (target, input, policy, format) = selection.finish()?;
+    Ok(Command::Run(RunOptions {
+        target,
+        part,
+        input,
+        policy,
+        format,
+        jobs,
+    }))
+}
+
+fn parse_bench(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut selection = Selection::new();
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    while let Some(arg) = args.next() {
+        if selection.accept(&arg, args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--iterations" => iterations = next_number(arg, args)?,
+            "--threshold" => {
+                threshold = next_number(arg.clone(), args)?;
+                // NaN would never flag anything
+                if !threshold.is_finite() || threshold < 0.0 {
+                    return Err(CliError::InvalidNumber {
+                        flag: arg,
+                        value: threshold.to_string(),
+                    });
+                }
+            }
+            "--baseline" => {
+                baseline = Some(PathBuf::from(
+                    args.next().ok_or(CliError::MissingValue(arg))?,
+                ));
+            }
+            "--save-baseline" => {
+                save_baseline = Some(PathBuf::from(
+                    args.next().ok_or(CliError::MissingValue(arg))?,
+                ));
+            }
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    let (target, input, policy, format) = selection.finish()?;
+    Ok(Command::Bench(BenchOptions {
+        target,
+        input,
+        policy,
+        iterations,
+        baseline,
+        save_baseline,
+        threshold,
+        format,
+    }))
+}
+
+fn parse_verify(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut answers = PathBuf::from(ANSWERS_FILE_PATH);
+    let mut input = InputSource::Default;
+    let mut format = Format::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                answers = PathBuf::from(args.next().ok_or(CliError::MissingValue(arg))?);
+            }
+            "--input-dir" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg))?;
+                input = InputSource::Dir(PathBuf::from(value));
+            }
+            "--format" => format = next_format(arg, args)?,
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Verify {
+        answers,
+        input,
+        format,
+    })
+}
+
+fn parse_new(args: &mut impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let day = args.next().ok_or(CliError::MissingDay)?;
+    let day = day.parse::<u8>().map_err(|_| CliError::InvalidDay(day))?;
+    if let Some(arg) = args.next() {
+        return Err(CliError::UnexpectedArgument(arg));
+    }
+    Ok(Command::New { day })
+}
+
+// turns the arguments after the binary name into a command
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, CliError> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or(CliError::MissingCommand)?;
+    match command.as_str() {
+        "run" => parse_run(&mut args),
+        "verify" => parse_verify(&mut args),
+        "bench" => parse_bench(&mut args),
+        "new" => parse_new(&mut args),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => Err(CliError::UnknownCommand(command)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{BenchOptions, CliError, Command, Format, RunOptions, Target, parse_args};
+    use crate::{input::InputSource, parsing::ParsePolicy, solution::Part};
+
+    fn parse(args: &[&str]) -> Result<Command, CliError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_run_day() {
+        assert_eq!(
+            parse(&["run", "3"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(3),
+                part: None,
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+        assert_eq!(
+            parse(&["run", "5", "--part", "2"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(5),
+                part: Some(Part::Two),
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+        assert_eq!(
+            parse(&["run", "--part", "1", "5"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(5),
+                part: Some(Part::One),
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(
+            parse(&["run", "--all"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::All,
+                part: None,
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_input() {
+        assert_eq!(
+            parse(&["run", "2", "--input", "./example.txt"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(2),
+                part: None,
+                input: InputSource::Path(PathBuf::from("./example.txt")),
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+        assert_eq!(
+            parse(&["run", "2", "--input", "-"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(2),
+                part: None,
+                input: InputSource::Stdin,
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+        assert_eq!(
+            parse(&["run", "--all", "--input-dir", "./inputs"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::All,
+                part: None,
+                input: InputSource::Dir(PathBuf::from("./inputs")),
+                policy: ParsePolicy::Lenient,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_jobs() {
+        assert!(matches!(
+            parse(&["run", "--all", "--jobs", "4"]),
+            Ok(Command::Run(RunOptions { jobs: 4, .. }))
+        ));
+        assert_eq!(
+            parse(&["run", "--all", "--jobs", "0"]),
+            Err(CliError::InvalidNumber {
+                flag: "--jobs".into(),
+                value: "0".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_run_strict() {
+        assert_eq!(
+            parse(&["run", "4", "--strict"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(4),
+                part: None,
+                input: InputSource::Default,
+                policy: ParsePolicy::Strict,
+                format: Format::Text,
+                jobs: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            parse(&["run", "1", "--format", "json"]),
+            Ok(Command::Run(RunOptions {
+                target: Target::Day(1),
+                part: None,
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                format: Format::Json,
+                jobs: 1,
+            }))
+        );
+        assert_eq!(
+            parse(&["verify", "--format", "json"]),
+            Ok(Command::Verify {
+                answers: PathBuf::from("./answers.txt"),
+                input: InputSource::Default,
+                format: Format::Json,
+            })
+        );
+        assert!(matches!(
+            parse(&["bench", "--all", "--format", "json"]),
+            Ok(Command::Bench(BenchOptions {
+                format: Format::Json,
+                ..
+            }))
+        ));
+        assert_eq!(
+            parse(&["run", "1", "--format", "yaml"]),
+            Err(CliError::InvalidFormat("yaml".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse(&["verify"]),
+            Ok(Command::Verify {
+                answers: PathBuf::from("./answers.txt"),
+                input: InputSource::Default,
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "verify",
+                "--answers",
+                "./other.txt",
+                "--input-dir",
+                "./inputs"
+            ]),
+            Ok(Command::Verify {
+                answers: PathBuf::from("./other.txt"),
+                input: InputSource::Dir(PathBuf::from("./inputs")),
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&["bench", "2"]),
+            Ok(Command::Bench(BenchOptions {
+                target: Target::Day(2),
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                iterations: 100,
+                baseline: None,
+                save_baseline: None,
+                threshold: 10.0,
+                format: Format::Text,
+            }))
+        );
+        assert_eq!(
+            parse(&[
+                "bench",
+                "--all",
+                "--iterations",
+                "5",
+                "--baseline",
+                "./old.txt",
+                "--save-baseline",
+                "./new.txt",
+                "--threshold",
+                "2.5",
+            ]),
+            Ok(Command::Bench(BenchOptions {
+                target: Target::All,
+                input: InputSource::Default,
+                policy: ParsePolicy::Lenient,
+                iterations: 5,
+                baseline: Some(PathBuf::from("./old.txt")),
+                save_baseline: Some(PathBuf::from("./new.txt")),
+                threshold: 2.5,
+                format: Format::Text,
+            }))
+        );
+        assert_eq!(
+            parse(&["bench", "1", "--iterations", "lots"]),
+            Err(CliError::InvalidNumber {
+                flag: "--iterations".into(),
+                value: "lots".into()
+            })
+        );
+        for (threshold, shown) in [("NaN", "NaN"), ("-5", "-5"), ("inf", "inf")] {
+            assert_eq!(
+                parse(&["bench", "1", "--threshold", threshold]),
+                Err(CliError::InvalidNumber {
+                    flag: "--threshold".into(),
+                    value: shown.into()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(&["new", "7"]), Ok(Command::New { day: 7 }));
+        assert_eq!(parse(&["new"]), Err(CliError::MissingDay));
+        assert_eq!(
+            parse(&["new", "seven"]),
+            Err(CliError::InvalidDay("seven".into()))
+        );
+        assert_eq!(
+            parse(&["new", "7", "8"]),
+            Err(CliError::UnexpectedArgument("8".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse(&[]), Err(CliError::MissingCommand));
+        assert_eq!(
+            parse(&["walk"]),
+            Err(CliError::UnknownCommand("walk".into()))
+        );
+        assert_eq!(parse(&["run"]), Err(CliError::MissingTarget));
+        assert_eq!(
+            parse(&["run", "day1"]),
+            Err(CliError::InvalidDay("day1".into()))
+        );
+        assert_eq!(
+            parse(&["run", "1", "--part"]),
+            Err(CliError::MissingValue("--part".into()))
+        );
+        assert_eq!(
+            parse(&["run", "1", "--part", "3"]),
+            Err(CliError::InvalidPart("3".into()))
+        );
+        assert_eq!(
+            parse(&["run", "1", "2"]),
+            Err(CliError::UnexpectedArgument("2".into()))
+        );
+        assert_eq!(
+            parse(&["run", "--all", "--input", "-"]),
+            Err(CliError::InputWithAll)
+        );
+    }
+}
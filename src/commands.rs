@@ -0,0 +1,371 @@
+use std::{fs, path::Path};
+
+use crate::{
+    bench::{self, DayBench},
+    cli::{BenchOptions, Command, Format, RunOptions, Target, USAGE},
+    input::InputSource,
+    registry,
+    runner::{self, DayResult, PartResult},
+    scaffold,
+    solution::{DynSolution, Part, SolutionError},
+    value::Value,
+    verify::{self, Status},
+};
+
+fn implicit_unsolved(result: &PartResult, implicit: bool) -> bool {
+    implicit && matches!(result.answer, Err(SolutionError::Unsolved { .. }))
+}
+
+// a day fails if it couldn't be run or any part that was asked for errored
+fn day_succeeded(result: &Result<DayResult, SolutionError>, implicit: bool) -> bool {
+    match result {
+        Ok(result) => result
+            .parts
+            .iter()
+            .all(|part| part.answer.is_ok() || implicit_unsolved(part, implicit)),
+        Err(_) => false,
+    }
+}
+
+fn print_day(day: u8, result: &Result<DayResult, SolutionError>, implicit: bool) {
+    println!("Day {}", day);
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => {
+            eprintln!("Day {} failed: {}", day, error);
+            return;
+        }
+    };
+    for warning in &result.warnings {
+        eprintln!("Day {} skipped {}", day, warning);
+    }
+    for part in &result.parts {
+        match &part.answer {
+            Ok(answer) => println!("  Part {}: {}", part.part, answer),
+            Err(_) if implicit_unsolved(part, implicit) => {
+                println!("  Part {}: not solved", part.part)
+            }
+            Err(error) => eprintln!("Day {} part {} failed: {}", day, part.part, error),
+        }
+    }
+}
+
+// reports problems on stderr, then lines every part up in one table on stdout
+fn print_summary(results: &[(u8, Result<DayResult, SolutionError>)], implicit: bool) {
+    let mut rows = vec![[
+        "Day".to_string(),
+        "Part".to_string(),
+        "Answer".to_string(),
+        "Duration".to_string(),
+        "Status".to_string(),
+    ]];
+    for (day, result) in results {
+        let result = match result {
+            Ok(result) => result,
+            Err(error) => {
+                eprintln!("Day {} failed: {}", day, error);
+                let dash = || "-".to_string();
+                rows.push([
+                    day.to_string(),
+                    dash(),
+                    dash(),
+                    dash(),
+                    error.status().into(),
+                ]);
+                continue;
+            }
+        };
+        for warning in &result.warnings {
+            eprintln!("Day {} skipped {}", day, warning);
+        }
+        for part in &result.parts {
+            let (answer, status) = match &part.answer {
+                Ok(answer) => (answer.to_string(), "ok"),
+                Err(error) => {
+                    if !implicit_unsolved(part, implicit) {
+                        eprintln!("Day {} part {} failed: {}", day, part.part, error);
+                    }
+                    ("-".to_string(), error.status())
+                }
+            };
+            rows.push([
+                day.to_string(),
+                part.part.to_string(),
+                answer,
+                format!("{:.2?}", part.elapsed),
+                status.into(),
+            ]);
+        }
+    }
+
+    let widths: Vec<usize> = (0..5)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn failed_day_value(day: u8, error: &SolutionError) -> Value {
+    Value::from_entries(vec![
+        ("day", Value::Int(day as i64)),
+        ("status", Value::Str(error.status().into())),
+        ("error", Value::Str(error.to_string())),
+    ])
+}
+
+// prints the answers for every day, returning false if anything failed
+fn run_days(options: &RunOptions) -> Result<bool, String> {
+    let solutions = solutions_for(&options.target)?;
+    // only complain about unsolved parts when they were asked for directly
+    let implicit = options.part.is_none();
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    // run every day even after a failure, so all errors get reported
+    let results: Vec<(u8, Result<DayResult, SolutionError>)> = solutions
+        .iter()
+        .map(|solution| solution.day())
+        .zip(runner::run_all(
+            &solutions,
+            &options.input,
+            options.policy,
+            &parts,
+            options.jobs,
+        ))
+        .collect();
+    let succeeded = results
+        .iter()
+        .all(|(_, result)| day_succeeded(result, implicit));
+
+    match options.format {
+        Format::Text if options.target == Target::All => print_summary(&results, implicit),
+        Format::Text => {
+            for (day, result) in &results {
+                print_day(*day, result, implicit);
+            }
+        }
+        Format::Json => {
+            let days = results
+                .iter()
+                .map(|(day, result)| match result {
+                    Ok(result) => Value::from(result),
+                    Err(error) => failed_day_value(*day, error),
+                })
+                .collect();
+            let document = Value::from_entries(vec![
+                ("days", Value::List(days)),
+                ("succeeded", Value::Bool(succeeded)),
+            ]);
+            println!("{}", document.to_json());
+        }
+    }
+    Ok(succeeded)
+}
+
+fn describe(actual: &Result<i64, String>) -> String {
+    match actual {
+        Ok(answer) => answer.to_string(),
+        Err(error) => error.clone(),
+    }
+}
+
+// prints every verification, returning false if any answer was wrong
+fn verify_answers(answers: &Path, input: &InputSource, format: Format) -> Result<bool, String> {
+    let answers = fs::read_to_string(answers)
+        .map_err(|error| format!("Couldn't read '{}': {}", answers.display(), error))?;
+    let answers = verify::parse_answers(&answers)
+        .map_err(|error| format!("Invalid answers file, {}", error))?;
+    let verifications = verify::verify(registry::SOLUTIONS, &answers, input);
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for verification in &verifications {
+        match &verification.status {
+            Status::Pass => passed += 1,
+            Status::Fail { .. } => failed += 1,
+            Status::MissingAnswer { .. } | Status::MissingInput { .. } => missing += 1,
+        }
+    }
+
+    if format == Format::Json {
+        let document = Value::from_entries(vec![
+            (
+                "verifications",
+                Value::List(verifications.iter().map(Value::from).collect()),
+            ),
+            ("passed", Value::Int(passed)),
+            ("failed", Value::Int(failed)),
+            ("missing", Value::Int(missing)),
+            ("succeeded", Value::Bool(failed == 0)),
+        ]);
+        println!("{}", document.to_json());
+        return Ok(failed == 0);
+    }
+
+    for verification in &verifications {
+        let label = format!(
+            "day {} part {} {}",
+            verification.day, verification.part, verification.input
+        );
+        match &verification.status {
+            Status::Pass => println!("PASS    {}", label),
+            Status::Fail { expected, actual } => println!(
+                "FAIL    {}: expected {}, got {}",
+                label,
+                expected,
+                describe(actual)
+            ),
+            Status::MissingAnswer { actual } => println!(
+                "MISSING {}: no known answer, got {}",
+                label,
+                describe(actual)
+            ),
+            Status::MissingInput { reason } => println!("MISSING {}: {}", label, reason),
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    Ok(failed == 0)
+}
+
+fn solutions_for(target: &Target) -> Result<Vec<&'static dyn DynSolution>, String> {
+    match target {
+        Target::All => Ok(registry::SOLUTIONS.to_vec()),
+        Target::Day(day) => registry::find(*day)
+            .map(|solution| vec![solution])
+            .ok_or(format!("There is no solution for day {}", day)),
+    }
+}
+
+fn print_bench(day_bench: &DayBench, iterations: usize) {
+    println!("Day {} ({} iterations)", day_bench.day, iterations);
+    for timing in &day_bench.stages {
+        println!(
+            "  {:<6} min {:>10.2?}  median {:>10.2?}  p95 {:>10.2?}  allocs {:>8} ({} bytes)",
+            timing.stage.to_string(),
+            timing.stats.min,
+            timing.stats.median,
+            timing.stats.p95,
+            timing.allocations.count,
+            timing.allocations.bytes
+        );
+    }
+}
+
+// prints timings for every stage, returning false if anything failed or slowed down
+fn bench_days(options: &BenchOptions) -> Result<bool, String> {
+    let baseline = match &options.baseline {
+        Some(path) => {
+            let baseline = fs::read_to_string(path)
+                .map_err(|error| format!("Couldn't read '{}': {}", path.display(), error))?;
+            bench::parse_baseline(&baseline)
+                .map_err(|error| format!("Invalid baseline file, {}", error))?
+        }
+        None => vec![],
+    };
+
+    let mut benches = vec![];
+    let mut errors = vec![];
+    for solution in solutions_for(&options.target)? {
+        let day = solution.day();
+        let result = options
+            .input
+            .read(day, solution.input_path())
+            .map_err(SolutionError::Input)
+            .and_then(|input| bench::bench(solution, &input, options.policy, options.iterations));
+        match result {
+            Ok(day_bench) => {
+                if options.format == Format::Text {
+                    print_bench(&day_bench, options.iterations);
+                }
+                benches.push(day_bench);
+            }
+            Err(error) => {
+                if options.format == Format::Text {
+                    eprintln!("Day {} failed: {}", day, error);
+                }
+                errors.push((day, error));
+            }
+        }
+    }
+
+    let slowdowns = bench::compare(&benches, &baseline, options.threshold);
+    let succeeded = errors.is_empty() && slowdowns.is_empty();
+    match options.format {
+        Format::Text => {
+            for slowdown in &slowdowns {
+                println!(
+                    "SLOWER  day {} {}: {:.2?} -> {:.2?} (+{:.1}%)",
+                    slowdown.day,
+                    slowdown.stage,
+                    slowdown.baseline,
+                    slowdown.current,
+                    slowdown.percent()
+                );
+            }
+        }
+        Format::Json => {
+            let errors = errors
+                .iter()
+                .map(|(day, error)| failed_day_value(*day, error))
+                .collect();
+            let document = Value::from_entries(vec![
+                ("iterations", Value::Int(options.iterations as i64)),
+                (
+                    "days",
+                    Value::List(benches.iter().map(Value::from).collect()),
+                ),
+                ("errors", Value::List(errors)),
+                (
+                    "slowdowns",
+                    Value::List(slowdowns.iter().map(Value::from).collect()),
+                ),
+                ("succeeded", Value::Bool(succeeded)),
+            ]);
+            println!("{}", document.to_json());
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        fs::write(path, bench::format_baseline(&benches))
+            .map_err(|error| format!("Couldn't write '{}': {}", path.display(), error))?;
+    }
+    Ok(succeeded)
+}
+
+// carries out a command, returning whether everything it checked succeeded
+// an error means the command itself couldn't be carried out
+pub fn execute(command: Command) -> Result<bool, String> {
+    match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        Command::Run(options) => run_days(&options),
+        Command::Verify {
+            answers,
+            input,
+            format,
+        } => verify_answers(&answers, &input, format),
+        Command::Bench(options) => bench_days(&options),
+        Command::New { day } => {
+            // relative to the crate root, like every other path the days use
+            let written =
+                scaffold::scaffold(Path::new("."), day).map_err(|error| error.to_string())?;
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            Ok(true)
+        }
+    }
+}
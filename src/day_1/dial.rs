@@ -1,16 +1,58 @@
 use crate::day_1::turn::Turn;
 use std::fmt::Display;
 
+#[derive(Debug, PartialEq)]
+pub enum DialError {
+    InvalidSize(i32),
+    InvalidStart { start: i32, size: i32 },
+}
+
+impl Display for DialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DialError::InvalidSize(size) => {
+                write!(f, "Invalid dial size {}, needs at least one position", size)
+            }
+            DialError::InvalidStart { start, size } => write!(
+                f,
+                "Invalid start {} for a dial of size {}, needs to be 0 to {}",
+                start,
+                size,
+                size - 1
+            ),
+        }
+    }
+}
+
 // a struct for storing the state of the dial
+// the value is always between 0 and size - 1
 #[derive(Debug)]
 pub struct Dial {
     pub value: i32,
+    size: i32,
 }
 
 impl Dial {
-    // dials start at 50 by default
+    // dials are 100 positions and start at 50 by default
     pub const fn new() -> Self {
-        Self { value: 50 }
+        Self {
+            value: 50,
+            size: 100,
+        }
+    }
+
+    pub fn with_size(size: i32, start: i32) -> Result<Self, DialError> {
+        if size < 1 {
+            return Err(DialError::InvalidSize(size));
+        }
+        if !(0..size).contains(&start) {
+            return Err(DialError::InvalidStart { start, size });
+        }
+        Ok(Self { value: start, size })
+    }
+
+    pub fn size(&self) -> i32 {
+        self.size
     }
 
     // used to count the total number of times we land on exactly zero
@@ -19,6 +61,12 @@ impl Dial {
     }
 }
 
+impl Default for Dial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Display for Dial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "D@{}", self.value)
@@ -29,42 +77,46 @@ impl Display for Dial {
 impl Dial {
     pub fn turn(&mut self, turn: Turn) -> i32 {
         // keep track of what we had at the start
-        let start = self.value;
+        // worked out in i64 so big dials can't overflow going past the end
+        let start = i64::from(self.value);
+        let size = i64::from(self.size);
 
         // simplify things a bit first
         // determine how many full rotations we have
-        let turn_size = turn.abs();
-        let full_rotations = turn_size / 100;
+        let turn_size = i64::from(turn.abs());
+        let full_rotations = turn_size / size;
 
         // each full rotation = one zero crossing
         let mut zero_crossings = full_rotations;
 
         // only turn the remainder
-        let adjustment = turn_size % 100;
-        self.value += match turn {
-            Turn::Left(_) => -adjustment,
-            Turn::Right(_) => adjustment,
-        };
+        let adjustment = turn_size % size;
+        let mut value = start
+            + match turn {
+                Turn::Left(_) => -adjustment,
+                Turn::Right(_) => adjustment,
+            };
 
         // case where we land exactly on zero from another number
-        if self.value == 0 && start != 0 {
+        if value == 0 && start != 0 {
             zero_crossings += 1;
-        } else if self.value < 0 {
+        } else if value < 0 {
             // case where we wrap below 0, not starting from 0
             if start != 0 {
                 zero_crossings += 1;
             }
             // adjust value to wrap around
-            self.value += 100;
-        } else if self.value >= 100 {
-            // case where we wrap above 99
+            value += size;
+        } else if value >= size {
+            // case where we wrap above the last position
             zero_crossings += 1;
             // adjust value to wrap around
-            self.value -= 100;
+            value -= size;
         }
+        self.value = value as i32;
 
         // return the number of zero crossings we found
-        zero_crossings
+        zero_crossings as i32
     }
 }
 
@@ -137,6 +189,120 @@ mod tests {
         assert_eq!(zero_count, 2);
     }
 
+    #[test]
+    fn test_dial_with_size() {
+        assert_eq!(
+            Dial::with_size(0, 0).unwrap_err(),
+            DialError::InvalidSize(0)
+        );
+        assert_eq!(
+            Dial::with_size(10, 10).unwrap_err(),
+            DialError::InvalidStart {
+                start: 10,
+                size: 10
+            }
+        );
+        assert!(Dial::with_size(10, -1).is_err());
+        let dial = Dial::with_size(10, 9).unwrap();
+        assert_eq!((dial.value, dial.size()), (9, 10));
+    }
+
+    #[test]
+    fn test_small_dial() {
+        let mut dial = Dial::with_size(10, 5).unwrap();
+        assert_eq!(dial.turn(Turn::Left(5)), 1);
+        assert_eq!(dial.value, 0);
+        assert_eq!(dial.turn(Turn::Left(10)), 1);
+        assert_eq!(dial.turn(Turn::Right(25)), 2);
+        assert_eq!(dial.value, 5);
+
+        // every click on a one position dial lands on zero
+        let mut dial = Dial::with_size(1, 0).unwrap();
+        assert_eq!(dial.turn(Turn::Left(7)), 7);
+        assert_eq!(dial.value, 0);
+    }
+
+    #[test]
+    fn test_huge_dial() {
+        let mut dial = Dial::with_size(i32::MAX, i32::MAX - 1).unwrap();
+        assert_eq!(dial.turn(Turn::Right(i32::MAX - 1)), 1);
+        assert_eq!(dial.value, i32::MAX - 2);
+    }
+
+    // the same turns, one click at a time
+    fn count_clicks(size: i32, start: i32, turns: &[Turn]) -> (i32, i32) {
+        let (mut value, mut zero_count, mut crossings) = (start, 0, 0);
+        for turn in turns {
+            let step = if let Turn::Left(_) = turn { -1 } else { 1 };
+            for _ in 0..turn.abs() {
+                value = (value + step).rem_euclid(size);
+                crossings += i32::from(value == 0);
+            }
+            zero_count += i32::from(value == 0);
+        }
+        (zero_count, crossings)
+    }
+
+    #[test]
+    fn test_turns_match_clicks() {
+        let turns: Vec<Turn> = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82", "R300", "L7",
+        ]
+        .iter()
+        .map(|s| Turn::try_from(*s).unwrap())
+        .collect();
+        for size in 1..=40 {
+            for start in [0, size / 2, size - 1] {
+                let mut dial = Dial::with_size(size, start).unwrap();
+                let (mut zero_count, mut crossings) = (0, 0);
+                for turn in &turns {
+                    crossings += dial.turn(*turn);
+                    zero_count += dial.zero_contribution();
+                }
+                assert_eq!(
+                    (zero_count, crossings),
+                    count_clicks(size, start, &turns),
+                    "size {} starting at {}",
+                    size,
+                    start
+                );
+            }
+        }
+    }
+
+    fn example(mut dial: Dial) -> (i32, i32, i32) {
+        let (zero_count, zero_crossings) = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .iter()
+        .map(|s| Turn::try_from(*s).unwrap())
+        .fold((0, 0), |(zero_count, total), turn| {
+            let crossings = dial.turn(turn);
+            (zero_count + dial.zero_contribution(), total + crossings)
+        });
+        (zero_count, zero_crossings, dial.value)
+    }
+
+    #[test]
+    fn example_from_aoc_size_10() {
+        assert_eq!(example(Dial::with_size(10, 5).unwrap()), (2, 46, 7));
+    }
+
+    #[test]
+    fn example_from_aoc_size_7() {
+        assert_eq!(example(Dial::with_size(7, 3).unwrap()), (2, 67, 2));
+    }
+
+    #[test]
+    fn example_from_aoc_size_256() {
+        assert_eq!(example(Dial::with_size(256, 0).unwrap()), (0, 2, 38));
+    }
+
+    #[test]
+    fn example_from_aoc_size_100() {
+        assert_eq!(example(Dial::with_size(100, 50).unwrap()), (3, 6, 32));
+    }
+
     #[test]
     fn example_from_aoc() {
         let mut dial = Dial::new();
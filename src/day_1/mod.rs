@@ -1,11 +1,18 @@
-mod dial;
-mod turn;
+pub mod dial;
+pub mod turn;
 
-use std::{fmt::Display, fs};
+use std::fmt::Display;
 
 use dial::Dial;
 use turn::Turn;
 
+use crate::{
+    parsing::{ParseReport, ParseWarning},
+    solution::{Answer, Solution, SolutionError},
+    value_struct,
+};
+
+#[derive(Debug, PartialEq)]
 pub struct TurnApplicationResult {
     pub zero_count: i32,
     pub total_crossings: i32,
@@ -30,28 +37,67 @@ impl Display for TurnApplicationResult {
     }
 }
 
+value_struct!(TurnApplicationResult {
+    zero_count,
+    total_crossings
+});
+
 const TURNS_FILE_PATH: &str = "./src/day_1/turns.txt";
 
-pub fn apply_turns() -> Result<TurnApplicationResult, std::io::Error> {
+// the worked example from the puzzle description
+const EXAMPLE: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+fn parse_turns(input: &str, report: &mut ParseReport) -> Result<Vec<Turn>, ParseWarning> {
+    let mut turns = vec![];
+    for (index, line) in input.lines().enumerate() {
+        match Turn::parse_line(line, index + 1) {
+            Ok(turn) => turns.push(turn),
+            Err(error) => report.warn(error.warning())?,
+        }
+    }
+    Ok(turns)
+}
+
+fn apply(turns: &[Turn]) -> TurnApplicationResult {
     let mut dial = Dial::new();
 
-    let result: TurnApplicationResult = fs::read_to_string(TURNS_FILE_PATH)?
-        .lines()
-        .map(Turn::try_from)
-        .filter_map(|turn| match turn {
-            Ok(t) => Some(t),
-            Err(e) => {
-                eprintln!("Skipping invalid turn: {}", e);
-                None
-            }
-        })
+    turns
+        .iter()
         .fold(TurnApplicationResult::empty(), |result, turn| {
-            let crossings = dial.turn(turn);
+            let crossings = dial.turn(*turn);
             TurnApplicationResult {
                 total_crossings: result.total_crossings + crossings,
                 zero_count: result.zero_count + dial.zero_contribution(),
             }
-        });
+        })
+}
+
+pub struct Day1;
 
-    Ok(result)
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const INPUT_PATH: &'static str = TURNS_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Vec<Turn>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ok(parse_turns(input, report)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(apply(input).zero_count as Answer)
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(apply(input).total_crossings as Answer)
+    }
 }
@@ -1,70 +1,119 @@
 use std::fmt::Display;
 
-// two different things can go wrong when parsing a turn
-#[derive(Debug)]
-pub enum TurnParseError {
-    InvalidDirection {
-        original: String,
-        invalid: String,
-    },
-    InvalidNumber {
-        original: String,
-        invalid: String,
-        error: std::num::ParseIntError,
-    },
+use crate::parsing::ParseWarning;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnParseErrorKind {
+    Empty,
+    InvalidDirection(char),
+    MissingDistance,
+    InvalidDistance(char),
+    DistanceTooLarge(String),
 }
 
-impl Display for TurnParseError {
+impl Display for TurnParseErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TurnParseError::InvalidDirection { original, invalid } => {
-                write!(
-                    f,
-                    "Failed to parse '{}'. Invalid direction '{}'",
-                    original, invalid
-                )
+            TurnParseErrorKind::Empty => write!(f, "Empty turn"),
+            TurnParseErrorKind::InvalidDirection(direction) => write!(
+                f,
+                "Invalid direction '{}', expected L or R",
+                direction.escape_debug()
+            ),
+            TurnParseErrorKind::MissingDistance => write!(f, "Missing distance"),
+            TurnParseErrorKind::InvalidDistance(char) => {
+                write!(f, "Invalid character '{}' in distance", char.escape_debug())
             }
-            TurnParseError::InvalidNumber {
-                original,
-                invalid,
-                error,
-            } => {
-                write!(
-                    f,
-                    "Failed to parse '{}'. Invalid number '{}': {}",
-                    original, invalid, error
-                )
+            TurnParseErrorKind::DistanceTooLarge(distance) => {
+                write!(f, "Distance '{}' is too large", distance)
             }
         }
     }
 }
 
-#[derive(Debug)]
+// lines and columns start at 1, columns count characters in the line as it was given
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnParseError {
+    pub line: usize,
+    pub column: usize,
+    pub original: String,
+    pub kind: TurnParseErrorKind,
+}
+
+impl TurnParseError {
+    pub fn warning(&self) -> ParseWarning {
+        ParseWarning::at(
+            self.line,
+            self.column,
+            format!("{} in '{}'", self.kind, self.original.escape_debug()),
+        )
+    }
+}
+
+impl Display for TurnParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.warning())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Turn {
     Left(i32),
     Right(i32),
 }
 
+impl Turn {
+    // a direction then a distance, like 'L68'
+    // also fine: lowercase directions, spaces or tabs around either part and a trailing '\r'
+    pub fn parse_line(value: &str, line: usize) -> Result<Self, TurnParseError> {
+        let error = |byte: usize, kind| TurnParseError {
+            line,
+            column: value[..byte].chars().count() + 1,
+            original: value.to_string(),
+            kind,
+        };
+        let is_blank = |char: char| char == ' ' || char == '\t' || char == '\r';
+        let mut chars = value.char_indices().skip_while(|(_, char)| is_blank(*char));
+
+        let Some((position, direction)) = chars.next() else {
+            return Err(error(value.len(), TurnParseErrorKind::Empty));
+        };
+        let turn: fn(i32) -> Turn = match direction {
+            'L' | 'l' => Turn::Left,
+            'R' | 'r' => Turn::Right,
+            other => return Err(error(position, TurnParseErrorKind::InvalidDirection(other))),
+        };
+
+        let mut chars = chars.skip_while(|(_, char)| is_blank(*char)).peekable();
+        let Some(&(start, _)) = chars.peek() else {
+            return Err(error(value.len(), TurnParseErrorKind::MissingDistance));
+        };
+        let mut end = start;
+        for (position, char) in chars.by_ref() {
+            match char {
+                '0'..='9' => end = position + 1,
+                char if is_blank(char) => break,
+                other => return Err(error(position, TurnParseErrorKind::InvalidDistance(other))),
+            }
+        }
+        // only blanks are allowed after the distance
+        if let Some((position, other)) = chars.find(|(_, char)| !is_blank(*char)) {
+            return Err(error(position, TurnParseErrorKind::InvalidDistance(other)));
+        }
+
+        let distance = &value[start..end];
+        distance
+            .parse()
+            .map(turn)
+            .map_err(|_| error(start, TurnParseErrorKind::DistanceTooLarge(distance.into())))
+    }
+}
+
 impl TryFrom<&str> for Turn {
     type Error = TurnParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (dir, num_str) = value.split_at(1);
-        let num: i32 = num_str
-            .parse()
-            .map_err(|err| TurnParseError::InvalidNumber {
-                original: value.into(),
-                invalid: num_str.into(),
-                error: err,
-            })?;
-        match dir {
-            "L" => Ok(Turn::Left(num)),
-            "R" => Ok(Turn::Right(num)),
-            other => Err(TurnParseError::InvalidDirection {
-                original: value.into(),
-                invalid: other.into(),
-            }),
-        }
+        Turn::parse_line(value, 1)
     }
 }
 
@@ -84,3 +133,64 @@ impl Turn {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Turn, TurnParseError, TurnParseErrorKind};
+
+    fn error(value: &str) -> (usize, TurnParseErrorKind) {
+        let error = Turn::try_from(value).unwrap_err();
+        (error.column, error.kind)
+    }
+
+    #[test]
+    fn test_parse_turn() {
+        assert_eq!(Turn::try_from("L68"), Ok(Turn::Left(68)));
+        assert_eq!(Turn::try_from("R0"), Ok(Turn::Right(0)));
+        assert_eq!(Turn::try_from("l10"), Ok(Turn::Left(10)));
+        assert_eq!(Turn::try_from("r10"), Ok(Turn::Right(10)));
+        assert_eq!(Turn::try_from("L 10"), Ok(Turn::Left(10)));
+        assert_eq!(Turn::try_from("R10\r"), Ok(Turn::Right(10)));
+        assert_eq!(Turn::try_from("\t L\t10 \r"), Ok(Turn::Left(10)));
+        assert_eq!(Turn::try_from("R2147483647"), Ok(Turn::Right(i32::MAX)));
+    }
+
+    #[test]
+    fn test_parse_turn_errors() {
+        assert_eq!(error(""), (1, TurnParseErrorKind::Empty));
+        assert_eq!(error(" \r"), (3, TurnParseErrorKind::Empty));
+        assert_eq!(error("X10"), (1, TurnParseErrorKind::InvalidDirection('X')));
+        // columns count characters, not bytes
+        assert_eq!(error("é10"), (1, TurnParseErrorKind::InvalidDirection('é')));
+        assert_eq!(error("Lé0"), (2, TurnParseErrorKind::InvalidDistance('é')));
+        assert_eq!(error("L"), (2, TurnParseErrorKind::MissingDistance));
+        assert_eq!(error("L -5"), (3, TurnParseErrorKind::InvalidDistance('-')));
+        assert_eq!(error("L1 0"), (4, TurnParseErrorKind::InvalidDistance('0')));
+        assert_eq!(error("R1x"), (3, TurnParseErrorKind::InvalidDistance('x')));
+        assert_eq!(
+            error("R 99999999999"),
+            (
+                3,
+                TurnParseErrorKind::DistanceTooLarge("99999999999".into())
+            )
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let error = Turn::parse_line("  Q5", 7).unwrap_err();
+        assert_eq!(
+            error,
+            TurnParseError {
+                line: 7,
+                column: 3,
+                original: "  Q5".into(),
+                kind: TurnParseErrorKind::InvalidDirection('Q')
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 7, column 3: Invalid direction 'Q', expected L or R in '  Q5'"
+        );
+    }
+}
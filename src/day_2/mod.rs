@@ -1,22 +1,65 @@
-use std::fs;
-
 pub mod range;
 
 use range::Range;
 
-pub fn sum_invalid_ids() -> Result<i64, std::io::Error> {
-    let total = fs::read_to_string("./src/day_2/input.txt")
-        .unwrap()
-        .split(',')
-        .filter_map(|range| match Range::try_from(range) {
-            Ok(r) => Some(r.find_invalid_ids()),
-            Err(e) => {
-                eprintln!("Skipping invalid range: {}", e);
-                None
+use crate::{
+    parsing::{ParseReport, ParseWarning},
+    solution::{Answer, Part, Solution, SolutionError},
+};
+
+const INPUT_FILE_PATH: &str = "./src/day_2/input.txt";
+
+// the worked example from the puzzle description
+const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124";
+
+// ranges are comma separated, but may be wrapped over several lines
+fn parse_ranges(input: &str, report: &mut ParseReport) -> Result<Vec<Range>, ParseWarning> {
+    let mut ranges = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        for range in line
+            .split(',')
+            .map(str::trim)
+            .filter(|range| !range.is_empty())
+        {
+            let range = report
+                .check(line_number, Range::try_from(range))
+                .map_err(|error| ParseWarning::new(line_number, error))?;
+            if let Some(range) = range {
+                ranges.push(range);
             }
+        }
+    }
+    Ok(ranges)
+}
+
+fn sum_ranges(ranges: &[Range]) -> i64 {
+    ranges.iter().flat_map(|r| r.find_invalid_ids()).sum()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Vec<Range>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ok(parse_ranges(input, report)?)
+    }
+
+    fn part_one(_: &Self::Input) -> Result<Answer, SolutionError> {
+        Err(SolutionError::Unsolved {
+            day: Self::DAY,
+            part: Part::One,
         })
-        .flatten()
-        .sum();
+    }
 
-    Ok(total)
+    // ids made of any repeated sequence, not just one repeated twice
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(sum_ranges(input))
+    }
 }
@@ -1,5 +1,7 @@
 use std::{fmt::Display, num::ParseIntError};
 
+use crate::value::{ConvertError, ConvertErrorKind, Value};
+
 #[derive(Debug)]
 pub enum RangeParseError {
     Format {
@@ -143,10 +145,37 @@ impl TryFrom<&str> for Range {
     }
 }
 
+impl From<&Range> for Value {
+    fn from(value: &Range) -> Self {
+        Value::from_entries(vec![
+            ("start", Value::from(value.start)),
+            ("end", Value::from(value.end)),
+        ])
+    }
+}
+
+// checks start isn't after end, the same as parsing one from text
+impl TryFrom<&Value> for Range {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let start: i64 = value.field("start")?;
+        let end: i64 = value.field("end")?;
+        if start > end {
+            return Err(ConvertError::new(ConvertErrorKind::Invalid(format!(
+                "Start ({}) is greater than end ({})",
+                start, end
+            ))));
+        }
+        Ok(Range { start, end })
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::{Range, id_is_invalid, is_repeated_n_times};
+    use crate::value::{ConvertError, ConvertErrorKind, Value};
 
     #[test]
     fn test_is_repeated_n_times() {
@@ -176,6 +205,22 @@ mod tests {
         assert_eq!(invalid_ids, vec![1010]);
     }
 
+    #[test]
+    fn test_range_value() {
+        let range = Range::of(11, 22);
+        let value = Value::from(&range);
+        assert_eq!(value.to_string(), r#"{"start": 11, "end": 22}"#);
+        assert_eq!(Range::try_from(&value).unwrap(), range);
+
+        let backwards = Value::parse(r#"{"start": 5, "end": 1}"#).unwrap();
+        assert_eq!(
+            Range::try_from(&backwards),
+            Err(ConvertError::new(ConvertErrorKind::Invalid(
+                "Start (5) is greater than end (1)".into()
+            )))
+        );
+    }
+
     #[test]
     fn test_ranges_from_aoc() {
         let ranges: Vec<Vec<i64>> = [
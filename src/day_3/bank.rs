@@ -24,11 +24,14 @@ impl TryFrom<&str> for Bank {
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let batteries: Vec<usize> = value
             .chars()
-            .filter_map(|char| match char.to_digit(10) {
-                Some(value) => value.try_into().ok(),
-                None => None,
+            .map(|char| match char.to_digit(10) {
+                Some(value) => Ok(value as usize),
+                None => Err(ParseBankError::InvalidBattery {
+                    original: value.into(),
+                    invalid: char,
+                }),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         match batteries.len() {
             0..2 => Err(ParseBankError::NotEnoughBatteries {
@@ -41,6 +44,10 @@ impl TryFrom<&str> for Bank {
 }
 
 impl Bank {
+    pub fn battery_count(&self) -> usize {
+        self.batteries.len()
+    }
+
     pub fn get_max_joltage_in_range(
         &self,
         from_index: usize,
@@ -91,11 +98,7 @@ impl Bank {
                     // add the joltage contribution to the running total
                     joltage += value * 10_usize.pow((enabled - battery) as u32 - 1);
                 }
-                Err(err) => {
-                    eprintln!(
-                        "Failed to get max joltage for bank {} with {} batteries enabled: {:?}",
-                        self, enabled, err
-                    );
+                Err(_) => {
                     return Err(GetMaxJoltageError::TooManyBatteriesEnabled {
                         batteries: self.to_string(),
                         enabled,
@@ -119,13 +122,19 @@ mod tests {
     fn test_parse_bank() {
         let bank = Bank::try_from("12345").unwrap();
         assert_eq!(bank.batteries, vec![1, 2, 3, 4, 5]);
-        let bank = Bank::try_from("123abc3def").unwrap();
-        assert_eq!(bank.batteries, vec![1, 2, 3, 3]);
-        let bank = Bank::try_from("1a").unwrap_err();
+        let bank = Bank::try_from("123abc3def").unwrap_err();
+        assert_eq!(
+            bank,
+            ParseBankError::InvalidBattery {
+                original: "123abc3def".into(),
+                invalid: 'a',
+            }
+        );
+        let bank = Bank::try_from("1").unwrap_err();
         assert_eq!(
             bank,
             ParseBankError::NotEnoughBatteries {
-                original: "1a".into(),
+                original: "1".into(),
                 required: 2,
             }
         )
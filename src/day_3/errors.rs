@@ -3,6 +3,7 @@ use std::fmt::Display;
 #[derive(Debug, PartialEq)]
 pub enum ParseBankError {
     NotEnoughBatteries { original: String, required: usize },
+    InvalidBattery { original: String, invalid: char },
 }
 
 impl Display for ParseBankError {
@@ -13,6 +14,11 @@ impl Display for ParseBankError {
                 "Not enough batteries in bank string '{}' (minimum required is {})",
                 original, required
             ),
+            ParseBankError::InvalidBattery { original, invalid } => write!(
+                f,
+                "Invalid battery '{}' in bank string '{}'",
+                invalid, original
+            ),
         }
     }
 }
@@ -1,28 +1,128 @@
-use std::fs;
 pub mod bank;
 pub mod errors;
 
 use bank::Bank;
+use errors::ParseBankError;
 
-pub fn find_total_max_joltage(batteries_enabled: usize) -> Result<usize, std::io::Error> {
-    let total = fs::read_to_string("./src/day_3/input.txt")?
-        .lines()
-        .filter_map(|line| {
-            let bank = match Bank::try_from(line) {
-                Ok(bank) => bank,
-                Err(error) => {
-                    eprintln!("{:?}", error);
-                    return None;
-                }
-            };
-            match bank.get_max_joltage(batteries_enabled) {
-                Ok(joltage) => Some(joltage),
-                Err(error) => {
-                    eprintln!("{:?}", error);
-                    None
-                }
-            }
+use crate::{
+    parsing::{ParseReport, ParseWarning},
+    solution::{Answer, Solution, SolutionError},
+};
+
+const INPUT_FILE_PATH: &str = "./src/day_3/input.txt";
+
+// part one switches on 2 batteries per bank and part two 12
+const PART_ONE_ENABLED: usize = 2;
+const PART_TWO_ENABLED: usize = 12;
+
+// the worked example from the puzzle description
+const EXAMPLE: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+// a bank needs at least as many batteries as will be switched on
+fn parse_bank(line: &str, enabled: usize) -> Result<Bank, ParseBankError> {
+    let bank = Bank::try_from(line)?;
+    if bank.battery_count() < enabled {
+        return Err(ParseBankError::NotEnoughBatteries {
+            original: line.into(),
+            required: enabled,
+        });
+    }
+    Ok(bank)
+}
+
+fn parse_banks(
+    input: &str,
+    report: &mut ParseReport,
+    enabled: usize,
+) -> Result<Vec<Bank>, ParseWarning> {
+    let mut banks = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let bank = report
+            .check(line_number, parse_bank(line, enabled))
+            .map_err(|error| ParseWarning::new(line_number, error))?;
+        if let Some(bank) = bank {
+            banks.push(bank);
+        }
+    }
+    Ok(banks)
+}
+
+// a bank with fewer batteries than are enabled is an error, parsing only checks for part one
+fn total_max_joltage(banks: &[Bank], enabled: usize) -> Result<usize, SolutionError> {
+    banks
+        .iter()
+        .map(|bank| {
+            bank.get_max_joltage(enabled)
+                .map_err(|error| SolutionError::Parse(error.to_string()))
         })
-        .sum();
-    Ok(total)
+        .sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Vec<Bank>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ok(parse_banks(input, report, PART_ONE_ENABLED)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(total_max_joltage(input, PART_ONE_ENABLED)? as Answer)
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(total_max_joltage(input, PART_TWO_ENABLED)? as Answer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day3, EXAMPLE, parse_banks};
+    use crate::{
+        parsing::ParseReport,
+        solution::{Solution, SolutionError},
+    };
+
+    #[test]
+    fn test_example() {
+        let banks = Day3::parse(EXAMPLE, &mut ParseReport::strict()).unwrap();
+        assert_eq!(Day3::part_one(&banks).unwrap(), 357);
+        assert_eq!(Day3::part_two(&banks).unwrap(), 3121910778619);
+    }
+
+    #[test]
+    fn test_short_bank() {
+        let input = "987654321111111\n1\n811111111111119";
+        assert_eq!(
+            parse_banks(input, &mut ParseReport::strict(), 2)
+                .unwrap_err()
+                .to_string(),
+            "line 2: Not enough batteries in bank string '1' (minimum required is 2)"
+        );
+
+        let mut report = ParseReport::lenient();
+        let banks = parse_banks(input, &mut report, 2).unwrap();
+        assert_eq!(banks.len(), 2);
+        assert_eq!(report.warnings()[0].line, 2);
+    }
+
+    #[test]
+    fn test_bank_too_short_for_part_two() {
+        let input = "987654321111111\n12345\n811111111111119";
+        let banks = Day3::parse(input, &mut ParseReport::strict()).unwrap();
+        // still counts for part one
+        assert_eq!(Day3::part_one(&banks).unwrap(), 98 + 45 + 89);
+        assert!(matches!(
+            Day3::part_two(&banks),
+            Err(SolutionError::Parse(_))
+        ));
+    }
 }
@@ -1,20 +1,61 @@
-use std::fs;
-
-use crate::day_4::paper_rolls::{PaperRollRemover, PaperRolls};
+use crate::{
+    day_4::paper_rolls::{PaperRollRemover, PaperRolls},
+    parsing::{ParseReport, ParseWarning},
+    solution::{Answer, Solution, SolutionError},
+};
 
 pub mod paper_rolls;
 
-pub fn count_rolls_touching_at_most() -> Result<u32, std::io::Error> {
-    let input = fs::read_to_string("./src/day_4/input.txt")?;
-    let rolls = PaperRolls::from(input);
-    let mut paper_roll_remover = PaperRollRemover {
-        rolls,
-        touching_at_most: 3,
-    };
-    let total_removed = paper_roll_remover.remove_all();
-    fs::write(
-        "./src/day_4/output.txt",
-        paper_roll_remover.rolls.to_string(),
-    )?;
-    Ok(total_removed)
+const INPUT_FILE_PATH: &str = "./src/day_4/input.txt";
+
+// the worked example from the puzzle description
+const EXAMPLE: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+// rolls with at most this many neighbours can be reached by a forklift
+const TOUCHING_AT_MOST: u32 = 3;
+
+fn parse_rolls(input: &str, report: &mut ParseReport) -> Result<PaperRolls, ParseWarning> {
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        report
+            .check(line_number, PaperRolls::check_row(line))
+            .map_err(|error| ParseWarning::new(line_number, error))?;
+    }
+    // anything lenient let through is read as an empty space
+    Ok(PaperRolls::from(input.to_string()))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = PaperRolls;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ok(parse_rolls(input, report)?)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(input.count_touching_at_most(TOUCHING_AT_MOST) as Answer)
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError> {
+        // removing is destructive, so work on a copy
+        let mut remover = PaperRollRemover {
+            rolls: input.clone(),
+            touching_at_most: TOUCHING_AT_MOST,
+        };
+        Ok(remover.remove_all() as Answer)
+    }
 }
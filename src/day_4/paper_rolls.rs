@@ -30,6 +30,44 @@ impl Display for PaperRolls {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum ParsePaperRollsError {
+    InvalidCell {
+        row: String,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Display for ParsePaperRollsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCell { row, column, found } => write!(
+                f,
+                "Invalid cell '{found}' at column {column} of row '{row}', expected '@' or '.'"
+            ),
+        }
+    }
+}
+
+impl PaperRolls {
+    // From treats anything that isn't a roll as empty, this finds what it would gloss over
+    pub fn check_row(row: &str) -> Result<(), ParsePaperRollsError> {
+        match row
+            .chars()
+            .enumerate()
+            .find(|(_, char)| *char != '@' && *char != '.')
+        {
+            Some((index, found)) => Err(ParsePaperRollsError::InvalidCell {
+                row: row.into(),
+                column: index + 1,
+                found,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
 impl From<String> for PaperRolls {
     fn from(value: String) -> Self {
         Self::new(
@@ -183,7 +221,7 @@ impl Iterator for PaperRollRemover {
 #[cfg(test)]
 pub mod test {
 
-    use crate::day_4::paper_rolls::PaperRollRemover;
+    use crate::day_4::paper_rolls::{PaperRollRemover, ParsePaperRollsError};
 
     use super::{PaperRolls, l};
 
@@ -222,6 +260,20 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn test_check_row() {
+        assert_eq!(PaperRolls::check_row("..@@.@@@@."), Ok(()));
+        assert_eq!(PaperRolls::check_row(""), Ok(()));
+        assert_eq!(
+            PaperRolls::check_row("..@x.@"),
+            Err(ParsePaperRollsError::InvalidCell {
+                row: "..@x.@".into(),
+                column: 4,
+                found: 'x'
+            })
+        );
+    }
+
     #[test]
     fn test_parse_example() {
         let rolls = PaperRolls::from(
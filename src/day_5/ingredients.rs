@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::day_2::range::RangeParseError;
+use crate::{day_2::range::RangeParseError, parsing::ParseReport};
 
 use super::super::day_2::range::Range;
 
@@ -24,7 +24,7 @@ impl Ingredients {
     pub fn merge_ranges(&self) -> Vec<Range> {
         // first, sort in descending order, so we can treat the vec like a stack
         let mut sorted_ranges = self.fresh_ranges.clone();
-        sorted_ranges.sort_by(|a, b| b.start.cmp(&a.start));
+        sorted_ranges.sort_by_key(|range| std::cmp::Reverse(range.start));
         // set up the return vector
         let mut merged_ranges = vec![];
         // if the list is empty, do an early return
@@ -57,7 +57,6 @@ impl Ingredients {
         merged_ranges
             .iter()
             .map(|range| {
-                println!("{range}");
                 let Range { start, end } = range;
                 (1 + end - start) as usize
             })
@@ -84,29 +83,29 @@ impl Display for ParseIngredientsError {
     }
 }
 
-impl TryFrom<String> for Ingredients {
-    type Error = ParseIngredientsError;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
+impl Ingredients {
+    // ranges, then an empty line, then ids, one per line
+    pub fn parse(value: &str, report: &mut ParseReport) -> Result<Self, ParseIngredientsError> {
         let mut is_ranges = true;
         let mut fresh_ranges: Vec<Range> = Vec::new();
         let mut available: Vec<usize> = Vec::new();
-        for line in value.lines() {
+        for (index, line) in value.lines().enumerate() {
+            let line_number = index + 1;
             if line.is_empty() {
                 is_ranges = false;
                 continue;
             }
             if is_ranges {
-                match Range::try_from(line) {
-                    Ok(range) => fresh_ranges.push(range),
-                    Err(error) => {
-                        return Err(ParseIngredientsError::InvalidRange(error));
-                    }
+                let range = Range::try_from(line).map_err(ParseIngredientsError::InvalidRange);
+                if let Some(range) = report.check(line_number, range)? {
+                    fresh_ranges.push(range);
                 }
             } else {
-                match line.parse::<usize>() {
-                    Ok(value) => available.push(value),
-                    Err(error) => return Err(ParseIngredientsError::InvalidId(format!("{error}"))),
+                let id = line.parse::<usize>().map_err(|error| {
+                    ParseIngredientsError::InvalidId(format!("'{line}': {error}"))
+                });
+                if let Some(id) = report.check(line_number, id)? {
+                    available.push(id);
                 }
             }
         }
@@ -122,11 +121,19 @@ impl TryFrom<String> for Ingredients {
     }
 }
 
+impl TryFrom<String> for Ingredients {
+    type Error = ParseIngredientsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value, &mut ParseReport::strict())
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::day_2::range::Range;
+    use crate::{day_2::range::Range, parsing::ParseReport};
 
-    use super::Ingredients;
+    use super::{Ingredients, ParseIngredientsError};
 
     const TEST_INPUT: &str = "3-5
 10-14
@@ -143,10 +150,26 @@ mod test {
     fn test_parse() {
         let ingredients =
             Ingredients::try_from(TEST_INPUT.to_string()).expect("Should parse correctly");
-        assert_eq!(ingredients.fresh_ranges.len(), 4);
+        assert_eq!(ingredients.fresh_ranges.len(), 3);
         assert_eq!(ingredients.available.len(), 6);
     }
 
+    #[test]
+    fn test_parse_policy() {
+        let input = "3-5\n10-x\n\n1\nfive\n8";
+        assert!(matches!(
+            Ingredients::try_from(input.to_string()),
+            Err(ParseIngredientsError::InvalidRange(_))
+        ));
+
+        let mut report = ParseReport::lenient();
+        let ingredients = Ingredients::parse(input, &mut report).expect("Should skip bad lines");
+        assert_eq!(ingredients.fresh_ranges, vec![Range::of(3, 5)]);
+        assert_eq!(ingredients.available, vec![1, 8]);
+        let lines: Vec<usize> = report.warnings().iter().map(|w| w.line).collect();
+        assert_eq!(lines, vec![2, 5]);
+    }
+
     #[test]
     fn test_get_fresh() {
         let ingredients =
@@ -1,29 +1,43 @@
-use std::{fmt::Display, fs};
+use crate::{
+    day_5::ingredients::Ingredients,
+    parsing::ParseReport,
+    solution::{Answer, Solution, SolutionError},
+};
 
-use crate::day_5::ingredients::{Ingredients, ParseIngredientsError};
+pub mod ingredients;
 
-mod ingredients;
+const INPUT_FILE_PATH: &str = "./src/day_5/input.txt";
 
-#[derive(Debug)]
-pub enum CountFreshError {
-    Io(std::io::Error),
-    Parse(ParseIngredientsError),
-}
+// the worked example from the puzzle description
+const EXAMPLE: &str = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32";
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Ingredients;
 
-impl Display for CountFreshError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Io(error) => write!(f, "IO Error: {error}"),
-            Self::Parse(error) => write!(f, "Parse Error: {error}"),
-        }
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ingredients::parse(input, report).map_err(|error| SolutionError::Parse(error.to_string()))
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(input.get_fresh().len() as Answer)
     }
-}
 
-pub fn count_fresh_ingredients() -> Result<(usize, usize), CountFreshError> {
-    let input = fs::read_to_string("./src/day_5/input.txt").map_err(CountFreshError::Io)?;
-    let ingredients = Ingredients::try_from(input).map_err(CountFreshError::Parse)?;
-    Ok((
-        ingredients.get_fresh().len(),
-        ingredients.count_considered_fresh(),
-    ))
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(input.count_considered_fresh() as Answer)
+    }
 }
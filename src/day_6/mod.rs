@@ -1,8 +1,12 @@
-use std::{fmt::Display, fs};
+use std::fmt::Display;
 
-use crate::day_6::{
-    equation::{Equation, Operator},
-    matrix::Matrix,
+use crate::{
+    day_6::{
+        equation::{Equation, Operator},
+        matrix::Matrix,
+    },
+    parsing::ParseReport,
+    solution::{Answer, Part, Solution, SolutionError},
 };
 
 pub mod equation;
@@ -11,11 +15,27 @@ pub mod matrix;
 #[derive(Debug)]
 pub enum ParseError {
     NoRows,
-    IoError(std::io::Error),
     InvalidEquation(String),
 }
 
-pub fn parse_input(input: String) -> Result<Vec<Equation>, ParseError> {
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoRows => write!(f, "ParseError::NoRows"),
+            Self::InvalidEquation(message) => write!(f, "ParseError::InvalidEquation({message})"),
+        }
+    }
+}
+
+const INPUT_FILE_PATH: &str = "./src/day_6/input.txt";
+
+// the worked example from the puzzle description
+const EXAMPLE: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+pub fn parse_input(input: String, report: &mut ParseReport) -> Result<Vec<Equation>, ParseError> {
     let rows: Vec<Vec<&str>> = input
         .lines()
         .map(|line| line.split_whitespace().collect())
@@ -25,6 +45,15 @@ pub fn parse_input(input: String) -> Result<Vec<Equation>, ParseError> {
     let Some(width) = rows.first().map(|row| row.len()) else {
         return Err(ParseError::NoRows);
     };
+    // a short row would shift every column after it, so there's nothing to skip past
+    if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+        return Err(ParseError::InvalidEquation(format!(
+            "Line {} has {} entries, expected {}",
+            index + 1,
+            row.len(),
+            width
+        )));
+    }
 
     let data: Vec<&str> = rows
         .iter()
@@ -47,12 +76,26 @@ pub fn parse_input(input: String) -> Result<Vec<Equation>, ParseError> {
             op => Err(ParseError::InvalidEquation(format!(
                 "Invalid operator '{op}'"
             ))),
-        }?;
-        let values: Result<Vec<usize>, _> =
-            column.iter().map(|value| value.parse::<usize>()).collect();
+        };
+        // the operator is always on the last line
+        let Some(operator) = report.check(height, operator)? else {
+            continue;
+        };
 
-        let values = values
-            .map_err(|error| ParseError::InvalidEquation(format!("Couldn't parse int: {error}")))?;
+        let mut values = Vec::new();
+        for (index, value) in column.iter().enumerate() {
+            let parsed = value.parse::<usize>().map_err(|error| {
+                ParseError::InvalidEquation(format!("Couldn't parse int '{value}': {error}"))
+            });
+            match report.check(index + 1, parsed)? {
+                Some(parsed) => values.push(parsed),
+                None => break,
+            }
+        }
+        // a lenient parse drops the whole equation rather than solve part of it
+        if values.len() != column.len() {
+            continue;
+        }
 
         equations.push(Equation { values, operator });
     }
@@ -60,15 +103,39 @@ pub fn parse_input(input: String) -> Result<Vec<Equation>, ParseError> {
     Ok(equations)
 }
 
-pub fn solve_equations() -> Result<usize, ParseError> {
-    let input = fs::read_to_string("./src/day_6/input.txt").map_err(ParseError::IoError)?;
-    let equations = parse_input(input)?;
-    Ok(equations.iter().map(|eq| eq.solve()).sum::<usize>())
+fn sum_solutions(equations: &[Equation]) -> usize {
+    equations.iter().map(|eq| eq.solve()).sum::<usize>()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        parse_input(input.to_string(), report)
+            .map_err(|error| SolutionError::Parse(error.to_string()))
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+        Ok(sum_solutions(input) as Answer)
+    }
+
+    fn part_two(_: &Self::Input) -> Result<Answer, SolutionError> {
+        Err(SolutionError::Unsolved {
+            day: Self::DAY,
+            part: Part::Two,
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::parse_input;
+    use super::{ParseError, parse_input};
+    use crate::parsing::ParseReport;
 
     #[test]
     fn test_parse() {
@@ -77,10 +144,38 @@ mod test {
   6 98  215 314
 *   +   *   +  "
             .to_string();
-        let result = parse_input(input).expect("Should not throw");
+        let result = parse_input(input, &mut ParseReport::strict()).expect("Should not throw");
         result.iter().for_each(|eq| {
             println!("{eq} = {}", eq.solve());
         });
         assert_eq!(result.iter().map(|eq| eq.solve()).sum::<usize>(), 4277556);
     }
+
+    #[test]
+    fn test_parse_policy() {
+        let input = "1 2 3
+4 x 6
++ * -"
+            .to_string();
+        assert!(matches!(
+            parse_input(input.clone(), &mut ParseReport::strict()),
+            Err(ParseError::InvalidEquation(_))
+        ));
+
+        let mut report = ParseReport::lenient();
+        let result = parse_input(input, &mut report).expect("Should skip bad columns");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].solve(), 5);
+        let lines: Vec<usize> = report.warnings().iter().map(|w| w.line).collect();
+        assert_eq!(lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let input = "1 2\n3\n+ *".to_string();
+        assert!(matches!(
+            parse_input(input, &mut ParseReport::lenient()),
+            Err(ParseError::InvalidEquation(_))
+        ));
+    }
 }
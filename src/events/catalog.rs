@@ -0,0 +1,284 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{Color, ColorParseError, DisplayData, Events, get_display_data};
+use crate::value::{Field, ParseValueError, Schema, Value, Violation};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventKind {
+    pub id: String,
+    pub display: DisplayData,
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Read { path: PathBuf, error: io::Error },
+    Parse(ParseValueError),
+    Invalid(Vec<Violation>),
+    Color { id: String, error: ColorParseError },
+    // entry is the position of the second one in the config
+    DuplicateId { id: String, entry: usize },
+}
+
+impl Display for CatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogError::Read { path, error } => {
+                write!(f, "Couldn't read '{}': {}", path.display(), error)
+            }
+            CatalogError::Parse(error) => write!(f, "Invalid event config, {}", error),
+            CatalogError::Invalid(violations) => {
+                write!(f, "Invalid event config:")?;
+                for violation in violations {
+                    write!(f, "\n  {}", violation)?;
+                }
+                Ok(())
+            }
+            CatalogError::Color { id, error } => {
+                write!(f, "Invalid color for event '{}': {}", id, error)
+            }
+            CatalogError::DuplicateId { id, entry } => {
+                write!(
+                    f,
+                    "Event '{}' is configured twice, again at entry {}",
+                    id, entry
+                )
+            }
+        }
+    }
+}
+
+// a list of {"id": ..., "label": ..., "color": ..., "icon": ...} with only icon optional
+fn config_schema() -> Schema {
+    let field = |name: &str, required: bool| Field {
+        name: name.into(),
+        schema: Schema::String,
+        required,
+    };
+    Schema::List {
+        items: Box::new(Schema::Map {
+            fields: vec![
+                field("id", true),
+                field("label", true),
+                field("color", true),
+                field("icon", false),
+            ],
+            rest: None,
+        }),
+        min_length: None,
+        max_length: None,
+    }
+}
+
+// every kind of event that can be shown, the built in ones plus any from config
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    kinds: Vec<EventKind>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self {
+            kinds: Events::ALL
+                .into_iter()
+                .map(|event| EventKind {
+                    id: event.id().to_string(),
+                    display: get_display_data(event),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Catalog {
+    // just the built in events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the built in events with the config added on top
+    pub fn load(path: &Path) -> Result<Self, CatalogError> {
+        let config = fs::read_to_string(path).map_err(|error| CatalogError::Read {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut catalog = Self::new();
+        catalog.extend_from_str(&config)?;
+        Ok(catalog)
+    }
+
+    // config can restyle a built in event by using its id, but can't name one event twice
+    // nothing is added unless the whole config is valid
+    pub fn extend_from_str(&mut self, config: &str) -> Result<(), CatalogError> {
+        let config = Value::parse(config).map_err(CatalogError::Parse)?;
+        config_schema()
+            .validate(&config)
+            .map_err(CatalogError::Invalid)?;
+        let Value::List(entries) = config else {
+            unreachable!("The schema only allows a list");
+        };
+
+        let mut kinds: Vec<EventKind> = vec![];
+        for (entry, value) in entries.iter().enumerate() {
+            let text = |key: &str| value.field::<Option<String>>(key).ok().flatten();
+            let id = text("id").expect("The schema requires an id");
+            if kinds.iter().any(|kind| kind.id == id) {
+                return Err(CatalogError::DuplicateId { id, entry });
+            }
+            let color = text("color").expect("The schema requires a color");
+            let color = Color::try_from(color.as_str()).map_err(|error| CatalogError::Color {
+                id: id.clone(),
+                error,
+            })?;
+            kinds.push(EventKind {
+                id,
+                display: DisplayData {
+                    label: text("label").expect("The schema requires a label"),
+                    color,
+                    icon: text("icon"),
+                },
+            });
+        }
+
+        for kind in kinds {
+            match self
+                .kinds
+                .iter_mut()
+                .find(|existing| existing.id == kind.id)
+            {
+                Some(existing) => *existing = kind,
+                None => self.kinds.push(kind),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&EventKind> {
+        self.kinds.iter().find(|kind| kind.id == id)
+    }
+
+    pub fn display(&self, event: Events) -> &DisplayData {
+        &self
+            .get(event.id())
+            .expect("Built in events are always in the catalog")
+            .display
+    }
+
+    // built in events first, then config ones in the order they were added
+    pub fn kinds(&self) -> &[EventKind] {
+        &self.kinds
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Catalog, CatalogError};
+    use crate::events::{Color, ColorParseError, Events, NamedColor, RenderMode};
+
+    const CONFIG: &str = r##"[
+        {"id": "customs-hold", "label": "Customs Hold", "color": "#ff8800", "icon": "⚓"},
+        {"id": "bill-of-lading", "label": "B/L", "color": "bright-green"},
+        {"id": "delivered", "label": "Delivered", "color": "33"},
+    ]"##;
+
+    #[test]
+    fn test_built_in() {
+        let catalog = Catalog::new();
+        assert_eq!(catalog.kinds().len(), 2);
+        assert_eq!(
+            catalog.display(Events::DerivedFromCargo).label,
+            "Derived from Cargo"
+        );
+        assert!(catalog.get("customs-hold").is_none());
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut catalog = Catalog::new();
+        catalog.extend_from_str(CONFIG).unwrap();
+        let ids: Vec<&str> = catalog
+            .kinds()
+            .iter()
+            .map(|kind| kind.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "bill-of-lading",
+                "derived-from-cargo",
+                "customs-hold",
+                "delivered"
+            ]
+        );
+
+        let hold = &catalog.get("customs-hold").unwrap().display;
+        assert_eq!(hold.color, Color::Rgb(255, 136, 0));
+        assert_eq!(hold.render(RenderMode::Plain), "⚓ Customs Hold");
+        assert_eq!(
+            catalog.get("delivered").unwrap().display.color,
+            Color::Ansi256(33)
+        );
+
+        // built in ids get restyled rather than duplicated
+        let lading = catalog.display(Events::BillOfLading);
+        assert_eq!(lading.label, "B/L");
+        assert_eq!(lading.color, Color::Named(NamedColor::BrightGreen));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut catalog = Catalog::new();
+        let error = catalog
+            .extend_from_str(
+                r#"[{"id": "a", "label": "A", "color": "red"}, {"id": "a", "label": "B", "color": "blue"}]"#,
+            )
+            .unwrap_err();
+        assert!(matches!(&error, CatalogError::DuplicateId { id, entry: 1 } if id == "a"));
+        assert_eq!(
+            error.to_string(),
+            "Event 'a' is configured twice, again at entry 1"
+        );
+        // nothing from a bad config is kept
+        assert_eq!(catalog, Catalog::new());
+
+        let error = catalog
+            .extend_from_str(r#"[{"id": "a", "label": "A", "color": "mauve"}]"#)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CatalogError::Color {
+                error: ColorParseError::UnknownName(_),
+                ..
+            }
+        ));
+
+        let error = catalog
+            .extend_from_str(r#"[{"id": "a", "colour": "red"}, 3]"#)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid event config:\n  At '[0]': Missing key 'label'\n  At '[0]': Missing key 'color'\n  At '[0]': Unexpected key 'colour'\n  At '[1]': Expected map, found int"
+        );
+
+        assert!(matches!(
+            catalog.extend_from_str("[{"),
+            Err(CatalogError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("aoc_events_{}.txt", std::process::id()));
+        std::fs::write(&path, CONFIG).unwrap();
+        let catalog = Catalog::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(catalog.unwrap().kinds().len(), 4);
+        assert!(matches!(
+            Catalog::load(&path),
+            Err(CatalogError::Read { .. })
+        ));
+    }
+}
@@ -0,0 +1,339 @@
+use std::{ffi::OsString, fmt::Display, io::IsTerminal};
+
+// the 16 colors every terminal has, in ANSI order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl NamedColor {
+    const ALL: [(NamedColor, &'static str); 16] = [
+        (NamedColor::Black, "black"),
+        (NamedColor::Red, "red"),
+        (NamedColor::Green, "green"),
+        (NamedColor::Yellow, "yellow"),
+        (NamedColor::Blue, "blue"),
+        (NamedColor::Magenta, "magenta"),
+        (NamedColor::Cyan, "cyan"),
+        (NamedColor::White, "white"),
+        (NamedColor::BrightBlack, "bright-black"),
+        (NamedColor::BrightRed, "bright-red"),
+        (NamedColor::BrightGreen, "bright-green"),
+        (NamedColor::BrightYellow, "bright-yellow"),
+        (NamedColor::BrightBlue, "bright-blue"),
+        (NamedColor::BrightMagenta, "bright-magenta"),
+        (NamedColor::BrightCyan, "bright-cyan"),
+        (NamedColor::BrightWhite, "bright-white"),
+    ];
+
+    // also its number in the 256 color palette
+    fn index(&self) -> u8 {
+        NamedColor::ALL
+            .iter()
+            .position(|(color, _)| color == self)
+            .expect("Every color is listed") as u8
+    }
+
+    pub fn name(&self) -> &'static str {
+        NamedColor::ALL[self.index() as usize].1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Named(NamedColor),
+    // the xterm 256 color palette
+    Ansi256(u8),
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ColorParseError {
+    Empty,
+    UnknownName(String),
+    InvalidHex(String),
+    InvalidIndex(String),
+}
+
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorParseError::Empty => write!(f, "Color is empty"),
+            ColorParseError::UnknownName(name) => write!(f, "Unknown color '{}'", name),
+            ColorParseError::InvalidHex(hex) => {
+                write!(f, "Invalid hex color '{}', expected #rrggbb", hex)
+            }
+            ColorParseError::InvalidIndex(index) => {
+                write!(f, "Invalid palette color '{}', expected 0 to 255", index)
+            }
+        }
+    }
+}
+
+// "green", "bright-red", "208" for the 256 palette or "#ff8800"
+impl TryFrom<&str> for Color {
+    type Error = ColorParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(ColorParseError::Empty);
+        }
+        if let Some(hex) = value.strip_prefix('#') {
+            // from_str_radix would also take a sign
+            let channel = |start: usize| {
+                hex.get(start..start + 2)
+                    .filter(|channel| channel.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                    .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            };
+            return match (hex.len(), channel(0), channel(2), channel(4)) {
+                (6, Some(red), Some(green), Some(blue)) => Ok(Color::Rgb(red, green, blue)),
+                _ => Err(ColorParseError::InvalidHex(value.to_string())),
+            };
+        }
+        if value.starts_with(|char: char| char.is_ascii_digit()) {
+            return value
+                .parse()
+                .map(Color::Ansi256)
+                .map_err(|_| ColorParseError::InvalidIndex(value.to_string()));
+        }
+        let name = value.to_lowercase().replace('_', "-");
+        NamedColor::ALL
+            .iter()
+            .find(|(_, known)| *known == name)
+            .map(|(color, _)| Color::Named(*color))
+            .ok_or(ColorParseError::UnknownName(value.to_string()))
+    }
+}
+
+// the same form it's parsed from
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::Named(named) => write!(f, "{}", named.name()),
+            Color::Ansi256(index) => write!(f, "{}", index),
+            Color::Rgb(red, green, blue) => write!(f, "#{:02x}{:02x}{:02x}", red, green, blue),
+        }
+    }
+}
+
+// xterm's values for the first 16 colors
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl Color {
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Named(named) => BASIC_RGB[named.index() as usize],
+            Color::Ansi256(index @ 0..16) => BASIC_RGB[*index as usize],
+            // a 6x6x6 cube, then 24 greys
+            Color::Ansi256(index @ 16..232) => {
+                let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+                let cube = index - 16;
+                (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+            }
+            Color::Ansi256(index) => {
+                let grey = 8 + (index - 232) * 10;
+                (grey, grey, grey)
+            }
+            Color::Rgb(red, green, blue) => (*red, *green, *blue),
+        }
+    }
+
+    // the parameters for a foreground color escape sequence
+    fn ansi_code(&self) -> String {
+        match self {
+            Color::Named(named) if named.index() < 8 => format!("{}", 30 + named.index()),
+            Color::Named(named) => format!("{}", 90 + named.index() - 8),
+            Color::Ansi256(index) => format!("38;5;{}", index),
+            Color::Rgb(red, green, blue) => format!("38;2;{};{};{}", red, green, blue),
+        }
+    }
+
+    // what css calls it, the basic names where css has the same one
+    fn css(&self) -> String {
+        match self {
+            Color::Named(named) if named.index() < 8 => named.name().to_string(),
+            other => {
+                let (red, green, blue) = other.to_rgb();
+                format!("#{:02x}{:02x}{:02x}", red, green, blue)
+            }
+        }
+    }
+
+    pub fn paint(&self, text: &str, mode: RenderMode) -> String {
+        match mode {
+            RenderMode::Plain => text.to_string(),
+            RenderMode::Ansi => format!("\x1b[{}m{}\x1b[0m", self.ansi_code(), text),
+            RenderMode::Html => format!(
+                "<span style=\"color: {}\">{}</span>",
+                self.css(),
+                escape_html(text)
+            ),
+        }
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    Plain,
+    Ansi,
+    Html,
+}
+
+impl RenderMode {
+    // colors for a terminal, unless NO_COLOR is set to anything or stdout is a file or pipe
+    pub fn detect() -> RenderMode {
+        RenderMode::for_terminal(
+            std::env::var_os("NO_COLOR"),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    pub fn for_terminal(no_color: Option<OsString>, is_terminal: bool) -> RenderMode {
+        let no_color = no_color.is_some_and(|value| !value.is_empty());
+        if is_terminal && !no_color {
+            RenderMode::Ansi
+        } else {
+            RenderMode::Plain
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::OsString;
+
+    use super::{Color, ColorParseError, NamedColor, RenderMode};
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(
+            Color::try_from("green"),
+            Ok(Color::Named(NamedColor::Green))
+        );
+        assert_eq!(
+            Color::try_from("Bright_Red"),
+            Ok(Color::Named(NamedColor::BrightRed))
+        );
+        assert_eq!(Color::try_from("208"), Ok(Color::Ansi256(208)));
+        assert_eq!(Color::try_from("#FF8800"), Ok(Color::Rgb(255, 136, 0)));
+        assert_eq!(
+            Color::try_from("#ff88"),
+            Err(ColorParseError::InvalidHex("#ff88".into()))
+        );
+        assert_eq!(
+            Color::try_from("#+f+f+f"),
+            Err(ColorParseError::InvalidHex("#+f+f+f".into()))
+        );
+        assert_eq!(
+            Color::try_from("256"),
+            Err(ColorParseError::InvalidIndex("256".into()))
+        );
+        assert_eq!(
+            Color::try_from("mauve"),
+            Err(ColorParseError::UnknownName("mauve".into()))
+        );
+        assert_eq!(Color::try_from(" "), Err(ColorParseError::Empty));
+        for color in ["bright-cyan", "17", "#0a0b0c"] {
+            assert_eq!(Color::try_from(color).unwrap().to_string(), color);
+        }
+    }
+
+    #[test]
+    fn test_to_rgb() {
+        assert_eq!(Color::Ansi256(1).to_rgb(), (205, 0, 0));
+        assert_eq!(Color::Ansi256(16).to_rgb(), (0, 0, 0));
+        assert_eq!(Color::Ansi256(208).to_rgb(), (255, 135, 0));
+        assert_eq!(Color::Ansi256(231).to_rgb(), (255, 255, 255));
+        assert_eq!(Color::Ansi256(244).to_rgb(), (128, 128, 128));
+    }
+
+    #[test]
+    fn test_paint() {
+        let green = Color::Named(NamedColor::Green);
+        assert_eq!(green.paint("ok", RenderMode::Plain), "ok");
+        assert_eq!(green.paint("ok", RenderMode::Ansi), "\x1b[32mok\x1b[0m");
+        assert_eq!(
+            Color::Named(NamedColor::BrightBlue).paint("ok", RenderMode::Ansi),
+            "\x1b[94mok\x1b[0m"
+        );
+        assert_eq!(
+            Color::Ansi256(208).paint("ok", RenderMode::Ansi),
+            "\x1b[38;5;208mok\x1b[0m"
+        );
+        assert_eq!(
+            Color::Rgb(1, 2, 3).paint("ok", RenderMode::Ansi),
+            "\x1b[38;2;1;2;3mok\x1b[0m"
+        );
+        assert_eq!(
+            green.paint("a < b & \"c\"", RenderMode::Html),
+            "<span style=\"color: green\">a &lt; b &amp; &quot;c&quot;</span>"
+        );
+        assert_eq!(
+            Color::Ansi256(208).paint("ok", RenderMode::Html),
+            "<span style=\"color: #ff8700\">ok</span>"
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(RenderMode::for_terminal(None, true), RenderMode::Ansi);
+        assert_eq!(RenderMode::for_terminal(None, false), RenderMode::Plain);
+        assert_eq!(
+            RenderMode::for_terminal(Some(OsString::from("1")), true),
+            RenderMode::Plain
+        );
+        // an empty NO_COLOR doesn't count
+        assert_eq!(
+            RenderMode::for_terminal(Some(OsString::new()), true),
+            RenderMode::Ansi
+        );
+    }
+}
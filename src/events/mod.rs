@@ -0,0 +1,129 @@
+mod catalog;
+mod color;
+mod timeline;
+
+pub use catalog::{Catalog, CatalogError, EventKind};
+pub use color::{Color, ColorParseError, NamedColor, RenderMode};
+pub use timeline::{
+    Entry, Timeline, TimelineQuery, format_timestamp, write_json_lines, write_listing,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Events {
+    BillOfLading,
+    DerivedFromCargo,
+}
+
+impl Events {
+    pub const ALL: [Events; 2] = [Events::BillOfLading, Events::DerivedFromCargo];
+
+    // how the event is referred to in config and exports
+    pub fn id(&self) -> &'static str {
+        match self {
+            Events::BillOfLading => "bill-of-lading",
+            Events::DerivedFromCargo => "derived-from-cargo",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownEvent(pub String);
+
+impl std::fmt::Display for UnknownEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown event '{}'", self.0)
+    }
+}
+
+impl TryFrom<&str> for Events {
+    type Error = UnknownEvent;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Events::ALL
+            .into_iter()
+            .find(|event| event.id() == value)
+            .ok_or(UnknownEvent(value.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayData {
+    pub label: String,
+    pub color: Color,
+    pub icon: Option<String>,
+}
+
+impl DisplayData {
+    // the icon goes in front, uncolored
+    pub fn render(&self, mode: RenderMode) -> String {
+        let label = self.color.paint(&self.label, mode);
+        match &self.icon {
+            Some(icon) if mode == RenderMode::Html => {
+                format!("{} {}", color::escape_html(icon), label)
+            }
+            Some(icon) => format!("{} {}", icon, label),
+            None => label,
+        }
+    }
+}
+
+pub fn get_display_data(event: Events) -> DisplayData {
+    match event {
+        Events::BillOfLading => DisplayData {
+            label: "Bill of Lading".into(),
+            color: Color::Named(NamedColor::Green),
+            icon: None,
+        },
+        Events::DerivedFromCargo => DisplayData {
+            label: "Derived from Cargo".into(),
+            color: Color::Named(NamedColor::Red),
+            icon: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Color, DisplayData, Events, NamedColor, RenderMode, UnknownEvent, get_display_data,
+    };
+
+    #[test]
+    fn test_render() {
+        let data = get_display_data(Events::BillOfLading);
+        assert_eq!(data.render(RenderMode::Plain), "Bill of Lading");
+        assert_eq!(
+            data.render(RenderMode::Ansi),
+            "\x1b[32mBill of Lading\x1b[0m"
+        );
+        assert_eq!(
+            get_display_data(Events::DerivedFromCargo).render(RenderMode::Html),
+            "<span style=\"color: red\">Derived from Cargo</span>"
+        );
+    }
+
+    #[test]
+    fn test_render_icon() {
+        let data = DisplayData {
+            label: "Hold".into(),
+            color: Color::Named(NamedColor::Yellow),
+            icon: Some("<script>&".into()),
+        };
+        assert_eq!(data.render(RenderMode::Plain), "<script>& Hold");
+        assert_eq!(
+            data.render(RenderMode::Html),
+            "&lt;script&gt;&amp; <span style=\"color: yellow\">Hold</span>"
+        );
+    }
+
+    #[test]
+    fn test_event_ids() {
+        for event in Events::ALL {
+            assert_eq!(Events::try_from(event.id()), Ok(event));
+        }
+        assert_eq!(
+            Events::try_from("BillOfLading"),
+            Err(UnknownEvent("BillOfLading".into()))
+        );
+    }
+}
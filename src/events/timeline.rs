@@ -0,0 +1,363 @@
+use std::{
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{Catalog, RenderMode, color::escape_html};
+use crate::value::{Json, Value};
+
+// one thing that happened, kind is an event id from the catalog
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub timestamp: SystemTime,
+    pub kind: String,
+    pub source: String,
+    pub payload: Value,
+}
+
+fn epoch_millis(timestamp: SystemTime) -> i64 {
+    match timestamp.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_millis() as i64,
+        Err(before) => -(before.duration().as_millis() as i64),
+    }
+}
+
+// like 2025-12-01T05:00:00.000Z, always utc
+pub fn format_timestamp(timestamp: SystemTime) -> String {
+    let millis = epoch_millis(timestamp);
+    let (days, millis) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+    // days since the epoch to a date, counting in 400 year eras that start on march 1st
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+impl From<&Entry> for Value {
+    fn from(value: &Entry) -> Self {
+        Value::from_entries(vec![
+            ("timestamp", Value::Str(format_timestamp(value.timestamp))),
+            ("kind", Value::Str(value.kind.clone())),
+            ("source", Value::Str(value.source.clone())),
+            ("payload", value.payload.clone()),
+        ])
+    }
+}
+
+// which entries to pick out, an empty kinds list means every kind
+#[derive(Debug, Clone, Default)]
+pub struct TimelineQuery {
+    pub kinds: Vec<String>,
+    // inclusive
+    pub from: Option<SystemTime>,
+    // exclusive
+    pub until: Option<SystemTime>,
+}
+
+impl TimelineQuery {
+    pub fn matches(&self, entry: &Entry) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+            && self.from.is_none_or(|from| entry.timestamp >= from)
+            && self.until.is_none_or(|until| entry.timestamp < until)
+    }
+}
+
+// entries are only ever added, and kept in the order they were recorded
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    entries: Vec<Entry>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, kind: &str, source: &str, payload: Value) -> &Entry {
+        self.record_at(SystemTime::now(), kind, source, payload)
+    }
+
+    pub fn record_at(
+        &mut self,
+        timestamp: SystemTime,
+        kind: &str,
+        source: &str,
+        payload: Value,
+    ) -> &Entry {
+        self.entries.push(Entry {
+            timestamp,
+            kind: kind.to_string(),
+            source: source.to_string(),
+            payload,
+        });
+        self.entries.last().expect("Just pushed an entry")
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn query<'a>(&'a self, query: &'a TimelineQuery) -> impl Iterator<Item = &'a Entry> {
+        self.entries.iter().filter(|entry| query.matches(entry))
+    }
+}
+
+// one json object per line
+pub fn write_json_lines<'a>(
+    entries: impl IntoIterator<Item = &'a Entry>,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    for entry in entries {
+        writeln!(output, "{}", Json(&Value::from(entry)))?;
+    }
+    Ok(())
+}
+
+// one line per entry, labelled and colored by the catalog, kinds it doesn't know show their id
+pub fn write_listing<'a>(
+    entries: impl IntoIterator<Item = &'a Entry>,
+    catalog: &Catalog,
+    mode: RenderMode,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    // the catalog escapes its own labels, everything else is escaped here
+    let text = |text: String| match mode {
+        RenderMode::Html => escape_html(&text),
+        _ => text,
+    };
+    for entry in entries {
+        let label = match catalog.get(&entry.kind) {
+            Some(kind) => kind.display.render(mode),
+            None => text(entry.kind.clone()),
+        };
+        write!(
+            output,
+            "{} {} from {}",
+            format_timestamp(entry.timestamp),
+            label,
+            text(entry.source.clone())
+        )?;
+        match entry.payload {
+            Value::Null => writeln!(output)?,
+            ref payload => writeln!(output, ": {}", text(payload.to_string()))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::{Timeline, TimelineQuery, format_timestamp, write_json_lines, write_listing};
+    use crate::{
+        events::{Catalog, Events, RenderMode},
+        value::Value,
+    };
+
+    // 2025-12-01T05:00:00Z, when the first puzzle unlocks
+    const DECEMBER_1ST: u64 = 1_764_565_200;
+
+    fn at_seconds(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn timeline() -> Timeline {
+        let mut timeline = Timeline::new();
+        let lading = Events::BillOfLading.id();
+        let cargo = Events::DerivedFromCargo.id();
+        timeline.record_at(
+            at_seconds(DECEMBER_1ST),
+            lading,
+            "port",
+            Value::from_entries(vec![("container", Value::Int(7))]),
+        );
+        timeline.record_at(at_seconds(DECEMBER_1ST + 60), cargo, "scanner", Value::Null);
+        timeline.record_at(
+            at_seconds(DECEMBER_1ST + 120),
+            lading,
+            "port",
+            Value::Str("amended".into()),
+        );
+        timeline.record_at(
+            at_seconds(DECEMBER_1ST + 180),
+            "customs-hold",
+            "customs",
+            Value::Null,
+        );
+        timeline
+    }
+
+    fn sources(timeline: &Timeline, query: &TimelineQuery) -> Vec<String> {
+        timeline
+            .query(query)
+            .map(|entry| format!("{}:{}", entry.kind, entry.source))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_timestamp(at_seconds(DECEMBER_1ST) + Duration::from_millis(1234)),
+            "2025-12-01T05:00:01.234Z"
+        );
+        assert_eq!(
+            format_timestamp(at_seconds(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_timestamp(UNIX_EPOCH - Duration::from_secs(1)),
+            "1969-12-31T23:59:59.000Z"
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let mut timeline = Timeline::new();
+        assert!(timeline.is_empty());
+        let entry = timeline.record(Events::BillOfLading.id(), "test", Value::Int(1));
+        assert_eq!(entry.kind, "bill-of-lading");
+        assert_eq!(timeline.len(), 1);
+    }
+
+    #[test]
+    fn test_query() {
+        let timeline = timeline();
+        assert_eq!(sources(&timeline, &TimelineQuery::default()).len(), 4);
+        assert_eq!(
+            sources(
+                &timeline,
+                &TimelineQuery {
+                    kinds: vec![Events::BillOfLading.id().into()],
+                    ..Default::default()
+                }
+            ),
+            vec!["bill-of-lading:port", "bill-of-lading:port"]
+        );
+        assert_eq!(
+            sources(
+                &timeline,
+                &TimelineQuery {
+                    from: Some(at_seconds(DECEMBER_1ST + 60)),
+                    until: Some(at_seconds(DECEMBER_1ST + 180)),
+                    ..Default::default()
+                }
+            ),
+            vec!["derived-from-cargo:scanner", "bill-of-lading:port"]
+        );
+        assert_eq!(
+            sources(
+                &timeline,
+                &TimelineQuery {
+                    kinds: vec!["customs-hold".into(), Events::DerivedFromCargo.id().into()],
+                    from: Some(at_seconds(DECEMBER_1ST + 61)),
+                    until: None,
+                }
+            ),
+            vec!["customs-hold:customs"]
+        );
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let timeline = timeline();
+        let mut output = vec![];
+        write_json_lines(timeline.entries().iter().take(2), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                r#"{"timestamp":"2025-12-01T05:00:00.000Z","kind":"bill-of-lading","source":"port","payload":{"container":7}}"#,
+                "\n",
+                r#"{"timestamp":"2025-12-01T05:01:00.000Z","kind":"derived-from-cargo","source":"scanner","payload":null}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_listing() {
+        let timeline = timeline();
+        let catalog = Catalog::new();
+        let mut output = vec![];
+        write_listing(timeline.entries(), &catalog, RenderMode::Plain, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "2025-12-01T05:00:00.000Z Bill of Lading from port: {\"container\": 7}\n\
+             2025-12-01T05:01:00.000Z Derived from Cargo from scanner\n\
+             2025-12-01T05:02:00.000Z Bill of Lading from port: \"amended\"\n\
+             2025-12-01T05:03:00.000Z customs-hold from customs\n"
+        );
+
+        let mut output = vec![];
+        write_listing(
+            timeline.entries().iter().skip(1).take(1),
+            &catalog,
+            RenderMode::Ansi,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "2025-12-01T05:01:00.000Z \x1b[31mDerived from Cargo\x1b[0m from scanner\n"
+        );
+    }
+
+    #[test]
+    fn test_listing_html() {
+        let mut timeline = Timeline::new();
+        timeline.record_at(
+            at_seconds(DECEMBER_1ST),
+            "<hold>",
+            "a & b",
+            Value::Str("<script>".into()),
+        );
+        timeline.record_at(
+            at_seconds(DECEMBER_1ST),
+            Events::BillOfLading.id(),
+            "<port>",
+            Value::Null,
+        );
+        let mut output = vec![];
+        write_listing(
+            timeline.entries(),
+            &Catalog::new(),
+            RenderMode::Html,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "2025-12-01T05:00:00.000Z &lt;hold&gt; from a &amp; b: &quot;&lt;script&gt;&quot;\n\
+             2025-12-01T05:00:00.000Z <span style=\"color: green\">Bill of Lading</span> from &lt;port&gt;\n"
+        );
+    }
+}
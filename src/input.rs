@@ -0,0 +1,132 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+// when set, inputs are read from <dir>/day_<N>.txt instead of next to the source
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    Read { path: PathBuf, error: io::Error },
+    Stdin(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Read { path, error } => {
+                write!(f, "Couldn't read '{}': {}", path.display(), error)
+            }
+            InputError::Stdin(error) => write!(f, "Couldn't read stdin: {}", error),
+        }
+    }
+}
+
+// where a day's puzzle input should come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    // the input directory from the environment if set, otherwise the day's own file
+    Default,
+    Path(PathBuf),
+    Dir(PathBuf),
+    Stdin,
+    Memory(String),
+}
+
+fn day_file(dir: PathBuf, day: u8) -> PathBuf {
+    dir.join(format!("day_{}.txt", day))
+}
+
+fn read_path(path: PathBuf) -> Result<String, InputError> {
+    fs::read_to_string(&path).map_err(|error| InputError::Read { path, error })
+}
+
+// picks the file the default source reads, given whatever the environment said
+fn default_path(day: u8, fallback: &str, input_dir: Option<PathBuf>) -> PathBuf {
+    match input_dir {
+        Some(dir) => day_file(dir, day),
+        None => PathBuf::from(fallback),
+    }
+}
+
+impl InputSource {
+    // fallback is the day's bundled input, used when nothing more specific was asked for
+    pub fn read(&self, day: u8, fallback: &str) -> Result<String, InputError> {
+        match self {
+            InputSource::Default => {
+                let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+                read_path(default_path(day, fallback, input_dir))
+            }
+            InputSource::Path(path) => read_path(path.clone()),
+            InputSource::Dir(dir) => read_path(day_file(dir.clone(), day)),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            InputSource::Memory(input) => Ok(input.clone()),
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    // "-" is the usual shorthand for stdin
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{InputError, InputSource, default_path};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(InputSource::from("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from("./in.txt"),
+            InputSource::Path(PathBuf::from("./in.txt"))
+        );
+    }
+
+    #[test]
+    fn test_default_path() {
+        assert_eq!(
+            default_path(3, "./src/day_3/input.txt", None),
+            PathBuf::from("./src/day_3/input.txt")
+        );
+        assert_eq!(
+            default_path(3, "./src/day_3/input.txt", Some(PathBuf::from("/inputs"))),
+            PathBuf::from("/inputs/day_3.txt")
+        );
+    }
+
+    #[test]
+    fn test_read_memory() {
+        let source = InputSource::Memory("L68\nR48".into());
+        assert_eq!(source.read(1, "./unused.txt").unwrap(), "L68\nR48");
+    }
+
+    #[test]
+    fn test_read_path() {
+        let source = InputSource::Path(PathBuf::from("./src/day_1/turns.txt"));
+        assert!(source.read(1, "./unused.txt").is_ok());
+
+        let source = InputSource::Dir(PathBuf::from("./does/not/exist"));
+        assert!(matches!(
+            source.read(1, "./unused.txt"),
+            Err(InputError::Read { path, .. }) if path.to_str() == Some("./does/not/exist/day_1.txt")
+        ));
+    }
+}
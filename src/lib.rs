@@ -0,0 +1,63 @@
+// the solutions and everything they are built from, the binary is a thin command line on top
+// only the modules and facades marked pub below are meant to be used from outside
+
+mod alloc_counter;
+mod bench;
+mod cli;
+mod commands;
+pub mod events;
+pub mod input;
+pub mod parsing;
+mod registry;
+mod runner;
+mod scaffold;
+mod solution;
+pub mod value;
+mod verify;
+
+mod day_1;
+mod day_2;
+mod day_3;
+mod day_4;
+mod day_5;
+mod day_6;
+// new days are declared above this line
+
+// the pieces worth reusing outside of the day they were written for
+
+pub mod dial {
+    pub use crate::day_1::{
+        dial::{Dial, DialError},
+        turn::{Turn, TurnParseError, TurnParseErrorKind},
+    };
+}
+
+pub mod grid {
+    pub use crate::day_4::paper_rolls::{
+        Location, PaperRollRemover, PaperRolls, ParsePaperRollsError,
+    };
+}
+
+pub mod ranges {
+    pub use crate::day_2::range::{Range, RangeParseError};
+}
+
+pub mod matrix {
+    pub use crate::day_6::matrix::{Matrix, MatrixIterator, MatrixIteratorDirection};
+}
+
+pub mod solutions {
+    pub use crate::{
+        registry::{SOLUTIONS, find},
+        solution::{Answer, DynSolution, Part, Solution, SolutionError},
+    };
+}
+
+// what the binary is built from
+pub mod app {
+    pub use crate::{
+        alloc_counter::CountingAllocator,
+        cli::{USAGE, parse_args},
+        commands::execute,
+    };
+}
@@ -0,0 +1,205 @@
+use std::fmt::Display;
+
+use crate::value::Value;
+
+// how parsers should treat a line they can't make sense of
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ParsePolicy {
+    // give up on the first problem
+    Strict,
+    // skip the problem, note it down and keep going
+    #[default]
+    Lenient,
+}
+
+impl TryFrom<&str> for ParsePolicy {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "strict" => Ok(ParsePolicy::Strict),
+            "lenient" => Ok(ParsePolicy::Lenient),
+            other => Err(other.into()),
+        }
+    }
+}
+
+// something a lenient parse skipped over, lines and columns start at 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseWarning {
+    pub fn new(line: usize, error: impl Display) -> Self {
+        Self {
+            line,
+            column: None,
+            message: error.to_string(),
+        }
+    }
+
+    // for parsers that know exactly where on the line things went wrong
+    pub fn at(line: usize, column: usize, error: impl Display) -> Self {
+        Self {
+            column: Some(column),
+            ..Self::new(line, error)
+        }
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {}: {}", self.line, column, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+impl From<&ParseWarning> for Value {
+    fn from(value: &ParseWarning) -> Self {
+        let mut entries = vec![("line", Value::Int(value.line as i64))];
+        entries.extend(
+            value
+                .column
+                .map(|column| ("column", Value::Int(column as i64))),
+        );
+        entries.push(("message", Value::Str(value.message.clone())));
+        Value::from_entries(entries)
+    }
+}
+
+// threaded through every parser so they all treat problems the same way
+#[derive(Debug, Default)]
+pub struct ParseReport {
+    pub policy: ParsePolicy,
+    warnings: Vec<ParseWarning>,
+}
+
+impl ParseReport {
+    pub fn new(policy: ParsePolicy) -> Self {
+        Self {
+            policy,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn strict() -> Self {
+        Self::new(ParsePolicy::Strict)
+    }
+
+    pub fn lenient() -> Self {
+        Self::new(ParsePolicy::Lenient)
+    }
+
+    // strict hands the error straight back, lenient records it and gives back nothing
+    pub fn check<T, E: Display>(
+        &mut self,
+        line: usize,
+        result: Result<T, E>,
+    ) -> Result<Option<T>, E> {
+        match (result, self.policy) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(error), ParsePolicy::Strict) => Err(error),
+            (Err(error), ParsePolicy::Lenient) => {
+                self.warnings.push(ParseWarning::new(line, error));
+                Ok(None)
+            }
+        }
+    }
+
+    // like check, for parsers that build their own warnings
+    pub fn warn(&mut self, warning: ParseWarning) -> Result<(), ParseWarning> {
+        match self.policy {
+            ParsePolicy::Strict => Err(warning),
+            ParsePolicy::Lenient => {
+                self.warnings.push(warning);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    pub fn into_warnings(self) -> Vec<ParseWarning> {
+        self.warnings
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParsePolicy, ParseReport, ParseWarning};
+    use crate::value::Value;
+
+    fn parse_all(report: &mut ParseReport, lines: &[&str]) -> Result<Vec<i32>, String> {
+        let mut values = vec![];
+        for (index, line) in lines.iter().enumerate() {
+            let parsed = line
+                .parse::<i32>()
+                .map_err(|error| format!("'{}' {}", line, error));
+            if let Some(value) = report.check(index + 1, parsed)? {
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(ParsePolicy::try_from("strict"), Ok(ParsePolicy::Strict));
+        assert_eq!(ParsePolicy::try_from("lenient"), Ok(ParsePolicy::Lenient));
+        assert_eq!(ParsePolicy::try_from("loose"), Err("loose".to_string()));
+    }
+
+    #[test]
+    fn test_strict() {
+        let mut report = ParseReport::strict();
+        let result = parse_all(&mut report, &["1", "x", "3", "y"]);
+        assert_eq!(result, Err("'x' invalid digit found in string".to_string()));
+        assert!(report.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_lenient() {
+        let mut report = ParseReport::lenient();
+        let result = parse_all(&mut report, &["1", "x", "3", "y"]);
+        assert_eq!(result, Ok(vec![1, 3]));
+        assert_eq!(
+            report.into_warnings(),
+            vec![
+                ParseWarning {
+                    line: 2,
+                    column: None,
+                    message: "'x' invalid digit found in string".into()
+                },
+                ParseWarning {
+                    line: 4,
+                    column: None,
+                    message: "'y' invalid digit found in string".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warn() {
+        let warning = ParseWarning::at(3, 2, "Bad direction");
+        assert_eq!(warning.to_string(), "line 3, column 2: Bad direction");
+        assert_eq!(
+            Value::from(&warning).to_string(),
+            r#"{"line": 3, "column": 2, "message": "Bad direction"}"#
+        );
+        assert_eq!(
+            ParseReport::strict().warn(warning.clone()),
+            Err(warning.clone())
+        );
+        let mut report = ParseReport::lenient();
+        assert_eq!(report.warn(warning.clone()), Ok(()));
+        assert_eq!(report.into_warnings(), vec![warning]);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::solution::DynSolution;
+
+// every day with a solution, in order
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &crate::day_1::Day1,
+    &crate::day_2::Day2,
+    &crate::day_3::Day3,
+    &crate::day_4::Day4,
+    &crate::day_5::Day5,
+    &crate::day_6::Day6,
+    // new days are registered above this line
+];
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SOLUTIONS, find};
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert_eq!(days[..6], [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(3).map(|solution| solution.day()), Some(3));
+        assert!(find(26).is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input::InputSource,
+    parsing::{ParsePolicy, ParseReport, ParseWarning},
+    solution::{Answer, DynSolution, Part, SolutionError},
+    value::Value,
+};
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer, SolutionError>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u8,
+    // anything a lenient parse skipped
+    pub warnings: Vec<ParseWarning>,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+impl From<&PartResult> for Value {
+    fn from(value: &PartResult) -> Self {
+        let mut entries = vec![
+            ("part", Value::from(value.part)),
+            ("duration_ns", Value::Int(value.elapsed.as_nanos() as i64)),
+        ];
+        match &value.answer {
+            Ok(answer) => {
+                entries.push(("status", Value::Str("ok".into())));
+                entries.push(("answer", Value::Int(*answer)));
+            }
+            Err(error) => {
+                entries.push(("status", Value::Str(error.status().into())));
+                entries.push(("error", Value::Str(error.to_string())));
+            }
+        }
+        Value::from_entries(entries)
+    }
+}
+
+impl From<&DayResult> for Value {
+    fn from(value: &DayResult) -> Self {
+        Value::from_entries(vec![
+            ("day", Value::Int(value.day as i64)),
+            ("status", Value::Str("ok".into())),
+            (
+                "parse_duration_ns",
+                Value::Int(value.parse_elapsed.as_nanos() as i64),
+            ),
+            (
+                "warnings",
+                Value::List(value.warnings.iter().map(Value::from).collect()),
+            ),
+            (
+                "parts",
+                Value::List(value.parts.iter().map(Value::from).collect()),
+            ),
+        ])
+    }
+}
+
+// reads and parses a day's input once, then answers each requested part
+// reading or parsing failing means no part can be answered, so that is the outer error
+pub fn run(
+    solution: &dyn DynSolution,
+    source: &InputSource,
+    policy: ParsePolicy,
+    parts: &[Part],
+) -> Result<DayResult, SolutionError> {
+    let input = source
+        .read(solution.day(), solution.input_path())
+        .map_err(SolutionError::Input)?;
+    let mut report = ParseReport::new(policy);
+    let start = Instant::now();
+    let parsed = solution.parse(&input, &mut report)?;
+    let parse_elapsed = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = solution.solve(parsed.as_ref(), *part);
+            PartResult {
+                part: *part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(DayResult {
+        day: solution.day(),
+        warnings: report.into_warnings(),
+        parse_elapsed,
+        parts,
+    })
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".into(),
+        },
+    }
+}
+
+// like run, but a panic anywhere in the day comes back as an error instead of unwinding
+pub fn run_isolated(
+    solution: &dyn DynSolution,
+    source: &InputSource,
+    policy: ParsePolicy,
+    parts: &[Part],
+) -> Result<DayResult, SolutionError> {
+    panic::catch_unwind(AssertUnwindSafe(|| run(solution, source, policy, parts)))
+        .unwrap_or_else(|payload| Err(SolutionError::Panic(panic_message(payload))))
+}
+
+// runs every solution on up to jobs threads, handing back results in the same order
+pub fn run_all(
+    solutions: &[&dyn DynSolution],
+    source: &InputSource,
+    policy: ParsePolicy,
+    parts: &[Part],
+    jobs: usize,
+) -> Vec<Result<DayResult, SolutionError>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<DayResult, SolutionError>>>> =
+        Mutex::new(solutions.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, solutions.len().max(1)) {
+            scope.spawn(|| {
+                // each worker takes the next day nobody has started yet
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(solution) = solutions.get(index) else {
+                        break;
+                    };
+                    let result = run_isolated(*solution, source, policy, parts);
+                    results.lock().expect("Workers don't panic")[index] = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .expect("Workers don't panic")
+        .into_iter()
+        .map(|result| result.expect("Every day should have been run"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run, run_all};
+    use crate::{
+        input::InputSource,
+        parsing::{ParsePolicy, ParseReport},
+        registry,
+        solution::{Answer, DynSolution, Part, Solution, SolutionError},
+    };
+
+    struct Panicker;
+
+    impl Solution for Panicker {
+        const DAY: u8 = 98;
+        const INPUT_PATH: &'static str = "./nowhere.txt";
+        const EXAMPLE: &'static str = "";
+        type Input = ();
+
+        fn parse(_: &str, _: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+            Ok(())
+        }
+
+        fn part_one(_: &Self::Input) -> Result<Answer, SolutionError> {
+            panic!("Part one fell over")
+        }
+
+        fn part_two(_: &Self::Input) -> Result<Answer, SolutionError> {
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn test_run_day_1() {
+        let solution = registry::find(1).unwrap();
+        let source = InputSource::from("./src/day_1/turns.txt");
+        let result = run(solution, &source, ParsePolicy::Strict, &Part::ALL)
+            .expect("Should read the committed turns");
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.parts.len(), 2);
+        assert_eq!(result.parts[0].part, Part::One);
+        assert_eq!(result.parts[1].part, Part::Two);
+        assert!(result.parts.iter().all(|part| part.answer.is_ok()));
+    }
+
+    #[test]
+    fn test_run_from_memory() {
+        let solution = registry::find(1).unwrap();
+        let source = InputSource::Memory("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".into());
+        let result = run(solution, &source, ParsePolicy::Strict, &[Part::Two]).unwrap();
+        assert_eq!(result.parts[0].answer.as_ref().unwrap(), &6);
+    }
+
+    #[test]
+    fn test_run_policy() {
+        let solution = registry::find(1).unwrap();
+        let source = InputSource::Memory("L68\nL30\nX48\nL5".into());
+        assert!(matches!(
+            run(solution, &source, ParsePolicy::Strict, &Part::ALL),
+            Err(SolutionError::Parse(_))
+        ));
+        let result = run(solution, &source, ParsePolicy::Lenient, &Part::ALL).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 3);
+    }
+
+    #[test]
+    fn test_run_missing_input() {
+        let solution = registry::find(5).unwrap();
+        let source = InputSource::from("./does/not/exist.txt");
+        assert!(matches!(
+            run(solution, &source, ParsePolicy::Lenient, &Part::ALL),
+            Err(SolutionError::Input(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_all() {
+        let day_1 = registry::find(1).unwrap();
+        let solutions: Vec<&dyn DynSolution> = vec![day_1, &Panicker, day_1, day_1];
+        let source = InputSource::Memory("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".into());
+        for jobs in [1, 3, 8] {
+            let results = run_all(&solutions, &source, ParsePolicy::Strict, &Part::ALL, jobs);
+            let days: Vec<Option<u8>> = results
+                .iter()
+                .map(|result| result.as_ref().ok().map(|result| result.day))
+                .collect();
+            // the panic only takes out its own day, and everything stays in order
+            assert_eq!(days, vec![Some(1), None, Some(1), Some(1)]);
+            assert!(matches!(
+                &results[1],
+                Err(SolutionError::Panic(message)) if message == "Part one fell over"
+            ));
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// every generated file has this swapped for the day number
+const DAY_PLACEHOLDER: &str = "__DAY__";
+
+// the lines new days are inserted above, so they stay in order
+pub const MODULES_MARKER: &str = "// new days are declared above this line";
+pub const REGISTRY_MARKER: &str = "// new days are registered above this line";
+
+const MOD_TEMPLATE: &str = r#"use crate::{
+    day___DAY__::entry::Entry,
+    parsing::{ParseReport, ParseWarning},
+    solution::{Answer, Part, Solution, SolutionError},
+};
+
+pub mod entry;
+
+const INPUT_FILE_PATH: &str = "./src/day___DAY__/input.txt";
+
+// the worked example from the puzzle description
+const EXAMPLE: &str = "1
+2
+3";
+
+fn parse_entries(input: &str, report: &mut ParseReport) -> Result<Vec<Entry>, ParseWarning> {
+    let mut entries = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let entry = report
+            .check(line_number, Entry::try_from(line))
+            .map_err(|error| ParseWarning::new(line_number, error))?;
+        if let Some(entry) = entry {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    const DAY: u8 = __DAY__;
+    const INPUT_PATH: &'static str = INPUT_FILE_PATH;
+    const EXAMPLE: &'static str = EXAMPLE;
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+        Ok(parse_entries(input, report)?)
+    }
+
+    fn part_one(_: &Self::Input) -> Result<Answer, SolutionError> {
+        Err(SolutionError::Unsolved {
+            day: Self::DAY,
+            part: Part::One,
+        })
+    }
+
+    fn part_two(_: &Self::Input) -> Result<Answer, SolutionError> {
+        Err(SolutionError::Unsolved {
+            day: Self::DAY,
+            part: Part::Two,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Day__DAY__, EXAMPLE};
+    use crate::{
+        parsing::ParseReport,
+        solution::{Solution, SolutionError},
+    };
+
+    #[test]
+    fn example_from_aoc() {
+        let input =
+            Day__DAY__::parse(EXAMPLE, &mut ParseReport::strict()).expect("Should parse correctly");
+        assert_eq!(input.len(), 3);
+        // swap these for the example answers as each part gets solved
+        assert!(matches!(
+            Day__DAY__::part_one(&input),
+            Err(SolutionError::Unsolved { .. })
+        ));
+        assert!(matches!(
+            Day__DAY__::part_two(&input),
+            Err(SolutionError::Unsolved { .. })
+        ));
+    }
+}
+"#;
+
+const ENTRY_TEMPLATE: &str = r#"use std::{fmt::Display, num::ParseIntError};
+
+#[derive(Debug)]
+pub enum ParseEntryError {
+    Empty,
+    Value {
+        original: String,
+        error: ParseIntError,
+    },
+}
+
+impl Display for ParseEntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseEntryError::Empty => write!(f, "Empty entry"),
+            ParseEntryError::Value { original, error } => {
+                write!(f, "Invalid entry '{}': {}", original, error)
+            }
+        }
+    }
+}
+
+// one line of the puzzle input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub value: i64,
+}
+
+impl TryFrom<&str> for Entry {
+    type Error = ParseEntryError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(ParseEntryError::Empty);
+        }
+        let parsed = trimmed.parse().map_err(|error| ParseEntryError::Value {
+            original: value.to_string(),
+            error,
+        })?;
+        Ok(Entry { value: parsed })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Entry, ParseEntryError};
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(Entry::try_from("42").unwrap(), Entry { value: 42 });
+        assert!(matches!(Entry::try_from(""), Err(ParseEntryError::Empty)));
+        assert!(matches!(
+            Entry::try_from("x"),
+            Err(ParseEntryError::Value { .. })
+        ));
+    }
+}
+"#;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u8),
+    AlreadyExists(PathBuf),
+    MissingMarker { path: PathBuf, marker: &'static str },
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => {
+                write!(f, "Invalid day {}, expected a day from 1 to 25", day)
+            }
+            ScaffoldError::AlreadyExists(path) => {
+                write!(f, "'{}' already exists", path.display())
+            }
+            ScaffoldError::MissingMarker { path, marker } => {
+                write!(f, "Couldn't find '{}' in '{}'", marker, path.display())
+            }
+            ScaffoldError::Io { path, error } => {
+                write!(f, "Couldn't access '{}': {}", path.display(), error)
+            }
+        }
+    }
+}
+
+fn render(template: &str, day: u8) -> String {
+    template.replace(DAY_PLACEHOLDER, &day.to_string())
+}
+
+// the day number of a module or registry line, e.g. 12 for 'mod day_12;'
+fn day_in(line: &str) -> Option<u8> {
+    let rest = &line[line.find("day_")? + 4..];
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+// adds the line above the first later day, or above the marker if there isn't one
+fn insert_line(contents: &str, marker: &'static str, line: &str, day: u8) -> Option<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let marker_index = lines.iter().position(|l| l.trim() == marker)?;
+    // only lines like the one being added count, anything else in between is left alone
+    let prefix = line.split("day_").next().unwrap_or(line);
+    let mut index = lines[..marker_index]
+        .iter()
+        .position(|l| l.trim_start().starts_with(prefix) && day_in(l).is_some_and(|d| d > day))
+        .unwrap_or(marker_index);
+    // attributes stay with the line they belong to
+    while index > 0 && lines[index - 1].trim_start().starts_with("#[") {
+        index -= 1;
+    }
+    let indent: String = lines[marker_index]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+
+    let mut output: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    output.insert(index, format!("{}{}", indent, line));
+    Some(output.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn register(
+    path: PathBuf,
+    marker: &'static str,
+    line: &str,
+    day: u8,
+) -> Result<(PathBuf, String), ScaffoldError> {
+    let contents = read(&path)?;
+    let updated =
+        insert_line(&contents, marker, line, day).ok_or(ScaffoldError::MissingMarker {
+            path: path.clone(),
+            marker,
+        })?;
+    Ok((path, updated))
+}
+
+// writes a new day module under root/src and registers it, returning every file touched
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let src = root.join("src");
+    let dir = src.join(format!("day_{}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::AlreadyExists(dir));
+    }
+
+    // work out every edit before writing anything, so a failure leaves nothing half done
+    let registrations = vec![
+        register(
+            src.join("lib.rs"),
+            MODULES_MARKER,
+            &format!("mod day_{};", day),
+            day,
+        )?,
+        register(
+            src.join("registry.rs"),
+            REGISTRY_MARKER,
+            &format!("&crate::day_{}::Day{},", day, day),
+            day,
+        )?,
+    ];
+
+    fs::create_dir_all(&dir).map_err(|error| ScaffoldError::Io {
+        path: dir.clone(),
+        error,
+    })?;
+    let files = [
+        (dir.join("mod.rs"), render(MOD_TEMPLATE, day)),
+        (dir.join("entry.rs"), render(ENTRY_TEMPLATE, day)),
+        // the real input goes here, it's left empty so running the day works straight away
+        (dir.join("input.txt"), String::new()),
+    ];
+    let mut written = vec![];
+    for (path, contents) in files.into_iter().chain(registrations) {
+        write(&path, &contents)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::{MODULES_MARKER, REGISTRY_MARKER, ScaffoldError, day_in, insert_line, scaffold};
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc_2025_scaffold_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            format!("mod day_1;\nmod day_3;\n{}\n", MODULES_MARKER),
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/registry.rs"),
+            format!(
+                "pub static SOLUTIONS = &[\n    &crate::day_1::Day1,\n    {}\n];\n",
+                REGISTRY_MARKER
+            ),
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_day_in() {
+        assert_eq!(day_in("mod day_12;"), Some(12));
+        assert_eq!(day_in("    &crate::day_3::Day3,"), Some(3));
+        assert_eq!(day_in("pub mod value;"), None);
+    }
+
+    #[test]
+    fn test_insert_line() {
+        let contents = format!("mod day_1;\nmod day_3;\n{}\n", MODULES_MARKER);
+        assert_eq!(
+            insert_line(&contents, MODULES_MARKER, "mod day_2;", 2).unwrap(),
+            format!("mod day_1;\nmod day_2;\nmod day_3;\n{}\n", MODULES_MARKER)
+        );
+        assert_eq!(
+            insert_line(&contents, MODULES_MARKER, "mod day_4;", 4).unwrap(),
+            format!("mod day_1;\nmod day_3;\nmod day_4;\n{}\n", MODULES_MARKER)
+        );
+        assert!(insert_line("mod day_1;\n", MODULES_MARKER, "mod day_2;", 2).is_none());
+
+        let contents = format!(
+            "mod day_1;\n#[allow(dead_code)]\nmod day_3;\n{}\n",
+            MODULES_MARKER
+        );
+        assert_eq!(
+            insert_line(&contents, MODULES_MARKER, "mod day_2;", 2).unwrap(),
+            format!(
+                "mod day_1;\nmod day_2;\n#[allow(dead_code)]\nmod day_3;\n{}\n",
+                MODULES_MARKER
+            )
+        );
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = temp_root("new");
+        let written = scaffold(&root, 2).expect("Should scaffold day 2");
+        assert_eq!(written.len(), 5);
+
+        let module = fs::read_to_string(root.join("src/day_2/mod.rs")).unwrap();
+        assert!(module.contains("pub struct Day2;"));
+        assert!(module.contains("const DAY: u8 = 2;"));
+        assert!(module.contains("\"./src/day_2/input.txt\""));
+        assert!(!module.contains("__DAY__"));
+        assert!(root.join("src/day_2/entry.rs").exists());
+        assert!(root.join("src/day_2/input.txt").exists());
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.starts_with("mod day_1;\nmod day_2;\nmod day_3;\n"));
+        let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        assert!(registry.contains("    &crate::day_1::Day1,\n    &crate::day_2::Day2,\n"));
+
+        assert!(matches!(
+            scaffold(&root, 2),
+            Err(ScaffoldError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            scaffold(&root, 0),
+            Err(ScaffoldError::InvalidDay(0))
+        ));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_missing_marker() {
+        let root = temp_root("marker");
+        fs::write(
+            root.join("src/registry.rs"),
+            "pub static SOLUTIONS = &[];\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            scaffold(&root, 4),
+            Err(ScaffoldError::MissingMarker { .. })
+        ));
+        // nothing gets written when registering would fail
+        assert!(!root.join("src/day_4").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
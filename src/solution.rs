@@ -0,0 +1,212 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    input::InputError,
+    parsing::{ParseReport, ParseWarning},
+    value::Value,
+};
+
+// every answer so far fits comfortably in an i64
+pub type Answer = i64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl From<Part> for Value {
+    fn from(value: Part) -> Self {
+        match value {
+            Part::One => Value::Int(1),
+            Part::Two => Value::Int(2),
+        }
+    }
+}
+
+impl TryFrom<&str> for Part {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(other.into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SolutionError {
+    Input(InputError),
+    Parse(String),
+    Unsolved { day: u8, part: Part },
+    // the solution panicked, caught so the other days still get to run
+    Panic(String),
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::Input(error) => write!(f, "Input Error: {}", error),
+            SolutionError::Parse(message) => write!(f, "Parse Error: {}", message),
+            SolutionError::Unsolved { day, part } => {
+                write!(f, "Day {} part {} has not been solved", day, part)
+            }
+            SolutionError::Panic(message) => write!(f, "Panicked: {}", message),
+        }
+    }
+}
+
+impl SolutionError {
+    // a one word summary, for tables and json output
+    pub fn status(&self) -> &'static str {
+        match self {
+            SolutionError::Unsolved { .. } => "unsolved",
+            SolutionError::Panic(_) => "panicked",
+            _ => "error",
+        }
+    }
+}
+
+// strict parsers give up with the same line aware message lenient ones would have kept
+impl From<ParseWarning> for SolutionError {
+    fn from(value: ParseWarning) -> Self {
+        SolutionError::Parse(value.to_string())
+    }
+}
+
+// the shape every day is expected to have:
+// parse the raw input once, then answer each part from the parsed form
+// parse follows the report's policy, leaving anything it skipped in the report
+pub trait Solution {
+    const DAY: u8;
+    const INPUT_PATH: &'static str;
+    const EXAMPLE: &'static str;
+    type Input: 'static;
+
+    fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError>;
+    fn part_one(input: &Self::Input) -> Result<Answer, SolutionError>;
+    fn part_two(input: &Self::Input) -> Result<Answer, SolutionError>;
+}
+
+// object safe version of Solution, so days with different input types can share a registry
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn input_path(&self) -> &'static str;
+    fn example(&self) -> &'static str;
+    fn parse(&self, input: &str, report: &mut ParseReport) -> Result<Box<dyn Any>, SolutionError>;
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<Answer, SolutionError>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input_path(&self) -> &'static str {
+        S::INPUT_PATH
+    }
+
+    fn example(&self) -> &'static str {
+        S::EXAMPLE
+    }
+
+    fn parse(&self, input: &str, report: &mut ParseReport) -> Result<Box<dyn Any>, SolutionError> {
+        let parsed = S::parse(input, report)?;
+        Ok(Box::new(parsed))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<Answer, SolutionError> {
+        // only ever handed back what our own parse produced
+        let input = parsed
+            .downcast_ref::<S::Input>()
+            .expect("parsed input should come from the same solution");
+        match part {
+            Part::One => S::part_one(input),
+            Part::Two => S::part_two(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answer, DynSolution, Part, Solution, SolutionError};
+    use crate::parsing::ParseReport;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        const DAY: u8 = 99;
+        const INPUT_PATH: &'static str = "./nowhere.txt";
+        const EXAMPLE: &'static str = "1\n2";
+        type Input = Vec<i64>;
+
+        fn parse(input: &str, report: &mut ParseReport) -> Result<Self::Input, SolutionError> {
+            let mut values = vec![];
+            for (index, line) in input.lines().enumerate() {
+                if let Some(value) = report
+                    .check(index + 1, line.parse::<i64>())
+                    .map_err(|error| SolutionError::Parse(error.to_string()))?
+                {
+                    values.push(value);
+                }
+            }
+            Ok(values)
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer, SolutionError> {
+            Ok(input.iter().sum::<i64>() * 2)
+        }
+
+        fn part_two(_: &Self::Input) -> Result<Answer, SolutionError> {
+            Err(SolutionError::Unsolved {
+                day: Self::DAY,
+                part: Part::Two,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(Part::try_from("1"), Ok(Part::One));
+        assert_eq!(Part::try_from("2"), Ok(Part::Two));
+        assert_eq!(Part::try_from("3"), Err("3".to_string()));
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let solution: &dyn DynSolution = &Doubler;
+        assert_eq!(solution.day(), 99);
+        let parsed = solution
+            .parse("1\n2\n3", &mut ParseReport::strict())
+            .expect("Should parse correctly");
+        assert_eq!(solution.solve(parsed.as_ref(), Part::One).unwrap(), 12);
+        assert!(matches!(
+            solution.solve(parsed.as_ref(), Part::Two),
+            Err(SolutionError::Unsolved { day: 99, .. })
+        ));
+        assert!(matches!(
+            solution.parse("one", &mut ParseReport::strict()),
+            Err(SolutionError::Parse(_))
+        ));
+
+        let mut report = ParseReport::lenient();
+        let parsed = solution.parse("1\none\n3", &mut report).unwrap();
+        assert_eq!(solution.solve(parsed.as_ref(), Part::One).unwrap(), 8);
+        assert_eq!(report.warnings().len(), 1);
+    }
+}
@@ -0,0 +1,498 @@
+use std::fmt::Display;
+
+use super::{KeyOrder, Map, Sign, Value};
+
+// one tag byte per value, then whatever that kind of value needs
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+// zigzag varint
+const INT: u8 = 3;
+// the 8 bytes of the float, little endian
+const FLOAT: u8 = 4;
+const NAN: u8 = 5;
+const INF_POSITIVE: u8 = 6;
+const INF_NEGATIVE: u8 = 7;
+const INF_UNSIGNED: u8 = 8;
+// float base then zigzag varint exponent
+const EXPONENT: u8 = 9;
+// plain varints
+const HEX: u8 = 10;
+const OCT: u8 = 11;
+const BIN: u8 = 12;
+// varint byte length then utf-8
+const STR: u8 = 13;
+// varint count then the items
+const LIST: u8 = 14;
+// varint count then key strings (without a tag) each followed by its value
+const MAP: u8 = 15;
+const SORTED_MAP: u8 = 16;
+
+// deeper than this is taken as garbage rather than risking the stack
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeErrorKind {
+    UnexpectedEnd,
+    UnknownTag(u8),
+    VarintOverflow,
+    InvalidUtf8,
+    ExponentOutOfRange(i64),
+    DuplicateKey(String),
+    TooDeep,
+    TrailingBytes,
+}
+
+impl Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeErrorKind::UnexpectedEnd => write!(f, "Unexpected end of input"),
+            DecodeErrorKind::UnknownTag(tag) => write!(f, "Unknown tag {}", tag),
+            DecodeErrorKind::VarintOverflow => write!(f, "Varint is too long"),
+            DecodeErrorKind::InvalidUtf8 => write!(f, "String isn't valid UTF-8"),
+            DecodeErrorKind::ExponentOutOfRange(exponent) => {
+                write!(f, "Exponent {} is out of range", exponent)
+            }
+            DecodeErrorKind::DuplicateKey(key) => write!(f, "Duplicate key '{}'", key),
+            DecodeErrorKind::TooDeep => write!(f, "Nested more than {} deep", MAX_DEPTH),
+            DecodeErrorKind::TrailingBytes => write!(f, "Unexpected bytes after value"),
+        }
+    }
+}
+
+// offset is the byte the problem was found at
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub kind: DecodeErrorKind,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "byte {}: {}", self.offset, self.kind)
+    }
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+// small negative numbers stay small: 0, -1, 1, -2 become 0, 1, 2, 3
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+fn write_str(output: &mut Vec<u8>, string: &str) {
+    write_varint(output, string.len() as u64);
+    output.extend_from_slice(string.as_bytes());
+}
+
+impl Value {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = vec![];
+        self.write_bytes(&mut output);
+        output
+    }
+
+    pub fn write_bytes(&self, output: &mut Vec<u8>) {
+        match self {
+            Value::Null => output.push(NULL),
+            Value::Bool(false) => output.push(FALSE),
+            Value::Bool(true) => output.push(TRUE),
+            Value::Int(int) => {
+                output.push(INT);
+                write_varint(output, zigzag(*int));
+            }
+            Value::Float(float) => {
+                output.push(FLOAT);
+                output.extend_from_slice(&float.to_le_bytes());
+            }
+            Value::NaN => output.push(NAN),
+            Value::Inf(Sign::Positive) => output.push(INF_POSITIVE),
+            Value::Inf(Sign::Negative) => output.push(INF_NEGATIVE),
+            Value::Inf(Sign::None) => output.push(INF_UNSIGNED),
+            Value::Exponent(base, exponent) => {
+                output.push(EXPONENT);
+                output.extend_from_slice(&base.to_le_bytes());
+                write_varint(output, zigzag(i64::from(*exponent)));
+            }
+            Value::Hex(n) | Value::Oct(n) | Value::Bin(n) => {
+                output.push(match self {
+                    Value::Hex(_) => HEX,
+                    Value::Oct(_) => OCT,
+                    _ => BIN,
+                });
+                write_varint(output, *n);
+            }
+            Value::Str(string) => {
+                output.push(STR);
+                write_str(output, string);
+            }
+            Value::List(list) => {
+                output.push(LIST);
+                write_varint(output, list.len() as u64);
+                for item in list {
+                    item.write_bytes(output);
+                }
+            }
+            Value::Map(map) => {
+                output.push(match map.order() {
+                    KeyOrder::Insertion => MAP,
+                    KeyOrder::Sorted => SORTED_MAP,
+                });
+                write_varint(output, map.len() as u64);
+                for (key, value) in map.iter() {
+                    write_str(output, key);
+                    value.write_bytes(output);
+                }
+            }
+        }
+    }
+
+    // the whole input has to be exactly one value
+    pub fn from_bytes(input: &[u8]) -> Result<Value, DecodeError> {
+        let mut decoder = Decoder { input, position: 0 };
+        let value = decoder.value(0)?;
+        if decoder.position < input.len() {
+            return Err(decoder.error(DecodeErrorKind::TrailingBytes));
+        }
+        Ok(value)
+    }
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Decoder<'_> {
+    fn error(&self, kind: DecodeErrorKind) -> DecodeError {
+        DecodeError {
+            offset: self.position,
+            kind,
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&[u8], DecodeError> {
+        if self.input.len() - self.position < count {
+            return Err(DecodeError {
+                offset: self.input.len(),
+                kind: DecodeErrorKind::UnexpectedEnd,
+            });
+        }
+        let bytes = &self.input[self.position..self.position + count];
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let start = self.position;
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            // the tenth byte only has room for the top bit
+            if shift == 63 && byte > 1 {
+                break;
+            }
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError {
+            offset: start,
+            kind: DecodeErrorKind::VarintOverflow,
+        })
+    }
+
+    fn float(&mut self) -> Result<f64, DecodeError> {
+        let bytes = self.take(8)?;
+        Ok(f64::from_le_bytes(
+            bytes.try_into().expect("Took exactly 8 bytes"),
+        ))
+    }
+
+    // a length can't be more than the bytes left, which stops huge allocations up front
+    fn length(&mut self) -> Result<usize, DecodeError> {
+        let length = self.varint()?;
+        if length > (self.input.len() - self.position) as u64 {
+            return Err(DecodeError {
+                offset: self.input.len(),
+                kind: DecodeErrorKind::UnexpectedEnd,
+            });
+        }
+        Ok(length as usize)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let length = self.length()?;
+        let start = self.position;
+        let bytes = self.take(length)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError {
+            offset: start,
+            kind: DecodeErrorKind::InvalidUtf8,
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error(DecodeErrorKind::TooDeep));
+        }
+        let tag = self.byte()?;
+        Ok(match tag {
+            NULL => Value::Null,
+            FALSE => Value::Bool(false),
+            TRUE => Value::Bool(true),
+            INT => Value::Int(unzigzag(self.varint()?)),
+            FLOAT => Value::Float(self.float()?),
+            NAN => Value::NaN,
+            INF_POSITIVE => Value::Inf(Sign::Positive),
+            INF_NEGATIVE => Value::Inf(Sign::Negative),
+            INF_UNSIGNED => Value::Inf(Sign::None),
+            EXPONENT => {
+                let base = self.float()?;
+                let start = self.position;
+                let exponent = unzigzag(self.varint()?);
+                let exponent = i32::try_from(exponent).map_err(|_| DecodeError {
+                    offset: start,
+                    kind: DecodeErrorKind::ExponentOutOfRange(exponent),
+                })?;
+                Value::Exponent(base, exponent)
+            }
+            HEX => Value::Hex(self.varint()?),
+            OCT => Value::Oct(self.varint()?),
+            BIN => Value::Bin(self.varint()?),
+            STR => Value::Str(self.string()?),
+            LIST => {
+                let count = self.length()?;
+                let mut list = Vec::with_capacity(count);
+                for _ in 0..count {
+                    list.push(self.value(depth + 1)?);
+                }
+                Value::List(list)
+            }
+            MAP | SORTED_MAP => {
+                let count = self.length()?;
+                let mut map = Map::with_order(if tag == MAP {
+                    KeyOrder::Insertion
+                } else {
+                    KeyOrder::Sorted
+                });
+                for _ in 0..count {
+                    let start = self.position;
+                    let key = self.string()?;
+                    let value = self.value(depth + 1)?;
+                    if map.contains_key(&key) {
+                        return Err(DecodeError {
+                            offset: start,
+                            kind: DecodeErrorKind::DuplicateKey(key),
+                        });
+                    }
+                    map.insert(key, value);
+                }
+                Value::Map(map)
+            }
+            tag => {
+                return Err(DecodeError {
+                    offset: self.position - 1,
+                    kind: DecodeErrorKind::UnknownTag(tag),
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DecodeError, DecodeErrorKind, MAX_DEPTH, unzigzag, zigzag};
+    use crate::value::{Map, Sign, Value};
+
+    // xorshift, so the random values are the same on every run
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, limit: u64) -> u64 {
+            self.next() % limit
+        }
+
+        fn string(&mut self) -> String {
+            let chars = ['a', 'Z', '"', '\\', '\n', '\u{1}', 'é', '🎄', ' '];
+            (0..self.below(6))
+                .map(|_| chars[self.below(chars.len() as u64) as usize])
+                .collect()
+        }
+
+        fn value(&mut self, depth: usize) -> Value {
+            let kinds = if depth > 3 { 12 } else { 14 };
+            match self.below(kinds) {
+                0 => Value::Null,
+                1 => Value::Bool(self.below(2) == 1),
+                2 => Value::Int(self.next() as i64 >> self.below(64)),
+                3 => Value::Float((self.next() as i64 >> self.below(64)) as f64 / 8.0),
+                4 => Value::NaN,
+                5 => {
+                    Value::Inf([Sign::Positive, Sign::Negative, Sign::None][self.below(3) as usize])
+                }
+                6 => Value::Exponent(
+                    (self.below(2000) as f64 - 1000.0) / 4.0,
+                    self.below(40) as i32 - 20,
+                ),
+                7 => Value::Hex(self.next() >> self.below(64)),
+                8 => Value::Oct(self.next() >> self.below(64)),
+                9 => Value::Bin(self.next() >> self.below(64)),
+                10 | 11 => Value::Str(self.string()),
+                12 => Value::List((0..self.below(5)).map(|_| self.value(depth + 1)).collect()),
+                _ => {
+                    let mut map = if self.below(2) == 0 {
+                        Map::new()
+                    } else {
+                        Map::sorted()
+                    };
+                    for _ in 0..self.below(5) {
+                        map.insert(self.string(), self.value(depth + 1));
+                    }
+                    Value::Map(map)
+                }
+            }
+        }
+    }
+
+    fn decode_error(bytes: &[u8]) -> DecodeError {
+        Value::from_bytes(bytes).unwrap_err()
+    }
+
+    #[test]
+    fn test_varints() {
+        for value in [0, 1, -1, 63, -64, 64, i64::MAX, i64::MIN] {
+            assert_eq!(unzigzag(zigzag(value)), value);
+        }
+        assert_eq!(Value::Int(-1).to_bytes(), vec![3, 1]);
+        assert_eq!(Value::Int(300).to_bytes(), vec![3, 0xD8, 0x04]);
+        assert_eq!(Value::Hex(u64::MAX).to_bytes().len(), 11);
+    }
+
+    #[test]
+    fn test_encoding() {
+        let value = Value::parse(r#"{"a": [0xFF, true], "b": null}"#).unwrap();
+        assert_eq!(
+            value.to_bytes(),
+            vec![15, 2, 1, b'a', 14, 2, 10, 0xFF, 0x01, 2, 1, b'b', 0]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_encodings() {
+        let value = Value::parse(
+            "[0x1F, 0o17, 0b101, 31, 1.5e3, 2.0, NaN, +Infinity, -Infinity, Infinity]",
+        )
+        .unwrap();
+        let decoded = Value::from_bytes(&value.to_bytes()).unwrap();
+        assert_eq!(decoded.to_string(), value.to_string());
+        assert!(matches!(decoded, Value::List(list) if list[0] == Value::Hex(31)));
+    }
+
+    #[test]
+    fn test_round_trip_random() {
+        let mut random = Random(0x2025_1201);
+        for _ in 0..500 {
+            let value = random.value(0);
+            let bytes = value.to_bytes();
+            let decoded = Value::from_bytes(&bytes).unwrap();
+            // the text form shows every variant, sign and key order, so it catches anything lost
+            assert_eq!(decoded.to_string(), value.to_string());
+            assert_eq!(decoded.to_bytes(), bytes);
+
+            // and anything read from text survives the trip through bytes
+            let parsed = Value::parse(&value.to_string()).unwrap();
+            let decoded = Value::from_bytes(&parsed.to_bytes()).unwrap();
+            assert_eq!(decoded.to_string(), parsed.to_string());
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut random = Random(7);
+        for _ in 0..100 {
+            let bytes = random.value(0).to_bytes();
+            for end in 0..bytes.len() {
+                assert_eq!(
+                    decode_error(&bytes[..end]).kind,
+                    DecodeErrorKind::UnexpectedEnd
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(
+            decode_error(&[14, 1, 99]),
+            DecodeError {
+                offset: 2,
+                kind: DecodeErrorKind::UnknownTag(99)
+            }
+        );
+        assert_eq!(
+            decode_error(&[0, 0]),
+            DecodeError {
+                offset: 1,
+                kind: DecodeErrorKind::TrailingBytes
+            }
+        );
+        assert_eq!(
+            decode_error(&[13, 1, 0xFF]).kind,
+            DecodeErrorKind::InvalidUtf8
+        );
+        assert_eq!(
+            decode_error(&[
+                3, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02
+            ])
+            .kind,
+            DecodeErrorKind::VarintOverflow
+        );
+        assert_eq!(
+            decode_error(&[15, 2, 1, b'a', 0, 1, b'a', 0]),
+            DecodeError {
+                offset: 5,
+                kind: DecodeErrorKind::DuplicateKey("a".into())
+            }
+        );
+        let mut exponent = vec![9];
+        exponent.extend_from_slice(&1.0f64.to_le_bytes());
+        exponent.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x10]);
+        assert!(matches!(
+            decode_error(&exponent).kind,
+            DecodeErrorKind::ExponentOutOfRange(_)
+        ));
+        // a count far bigger than the input is caught before anything is allocated
+        assert_eq!(
+            decode_error(&[14, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]).kind,
+            DecodeErrorKind::UnexpectedEnd
+        );
+        let deep: Vec<u8> = [14, 1]
+            .repeat(MAX_DEPTH + 1)
+            .into_iter()
+            .chain([0])
+            .collect();
+        assert_eq!(decode_error(&deep).kind, DecodeErrorKind::TooDeep);
+    }
+}
@@ -0,0 +1,468 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvertErrorKind {
+    WrongType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    OutOfRange {
+        value: String,
+        target: &'static str,
+    },
+    MissingKey(String),
+    // a string that should have been one of a fixed set of names
+    UnknownName(String),
+    // the right shape, but not a value the type allows
+    Invalid(String),
+}
+
+impl Display for ConvertErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvertErrorKind::WrongType { expected, found } => {
+                write!(f, "Expected {}, found {}", expected, found)
+            }
+            ConvertErrorKind::OutOfRange { value, target } => {
+                write!(f, "{} doesn't fit in {}", value, target)
+            }
+            ConvertErrorKind::MissingKey(key) => write!(f, "Missing key '{}'", key),
+            ConvertErrorKind::UnknownName(name) => write!(f, "Unknown name '{}'", name),
+            ConvertErrorKind::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+// a value that couldn't become the type asked for, path is where in the value it went wrong
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvertError {
+    pub path: String,
+    pub kind: ConvertErrorKind,
+}
+
+impl ConvertError {
+    pub fn new(kind: ConvertErrorKind) -> Self {
+        Self {
+            path: String::new(),
+            kind,
+        }
+    }
+
+    pub fn wrong_type(expected: &'static str, found: &Value) -> Self {
+        Self::new(ConvertErrorKind::WrongType {
+            expected,
+            found: found.kind_name(),
+        })
+    }
+
+    // puts a segment in front, as the error bubbles up out of nested values
+    fn within(mut self, segment: String) -> Self {
+        if !self.path.is_empty() && !self.path.starts_with('[') {
+            self.path.insert(0, '.');
+        }
+        self.path.insert_str(0, &segment);
+        self
+    }
+
+    pub fn within_key(self, key: &str) -> Self {
+        self.within(key.to_string())
+    }
+
+    pub fn within_index(self, index: usize) -> Self {
+        self.within(format!("[{}]", index))
+    }
+}
+
+impl Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "At '{}': {}", self.path, self.kind)
+        }
+    }
+}
+
+impl Value {
+    // what sort of value this is, for error messages
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Str(_) => "string",
+            Value::Float(_) => "float",
+            Value::NaN => "NaN",
+            Value::Inf(_) => "infinity",
+            Value::Exponent(..) => "exponent",
+            Value::Hex(_) => "hex",
+            Value::Oct(_) => "octal",
+            Value::Bin(_) => "binary",
+            Value::Bool(_) => "bool",
+            Value::Null => "null",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+        }
+    }
+
+    // converts the value under key, a missing key is treated as null so optional fields work
+    pub fn field<T>(&self, key: &str) -> Result<T, ConvertError>
+    where
+        T: for<'a> TryFrom<&'a Value, Error = ConvertError>,
+    {
+        let Value::Map(map) = self else {
+            return Err(ConvertError::wrong_type("map", self));
+        };
+        match map.get(key) {
+            Some(value) => T::try_from(value).map_err(|error| error.within_key(key)),
+            None => T::try_from(&Value::Null)
+                .map_err(|_| ConvertError::new(ConvertErrorKind::MissingKey(key.to_string()))),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Value {
+                fn from(value: $int) -> Self {
+                    Value::Int(i64::from(value))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+macro_rules! from_unsigned {
+    ($($int:ty),*) => {
+        $(
+            // too big for an int means it's kept exactly as hex instead
+            impl From<$int> for Value {
+                fn from(value: $int) -> Self {
+                    match i64::try_from(value) {
+                        Ok(int) => Value::Int(int),
+                        Err(_) => Value::Hex(value as u64),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u64, usize);
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::Float(f64::from(value))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Value::Map(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+// hash maps have no order of their own, so the keys come out sorted
+impl<K: Into<String>, T: Into<Value>> From<HashMap<K, T>> for Value {
+    fn from(value: HashMap<K, T>) -> Self {
+        let mut map = Map::sorted();
+        for (key, item) in value {
+            map.insert(key, item.into());
+        }
+        Value::Map(map)
+    }
+}
+
+// every integer encoding, widened so range checks can happen once
+fn as_integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Int(int) => Some(i128::from(*int)),
+        Value::Hex(n) | Value::Oct(n) | Value::Bin(n) => Some(i128::from(*n)),
+        _ => None,
+    }
+}
+
+macro_rules! try_into_int {
+    ($($int:ty),*) => {
+        $(
+            impl TryFrom<&Value> for $int {
+                type Error = ConvertError;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    let integer = as_integer(value)
+                        .ok_or_else(|| ConvertError::wrong_type("int", value))?;
+                    <$int>::try_from(integer).map_err(|_| {
+                        ConvertError::new(ConvertErrorKind::OutOfRange {
+                            value: value.to_string(),
+                            target: stringify!($int),
+                        })
+                    })
+                }
+            }
+        )*
+    };
+}
+
+try_into_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl TryFrom<&Value> for f64 {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .as_number()
+            .map(|number| number.as_f64())
+            .ok_or_else(|| ConvertError::wrong_type("number", value))
+    }
+}
+
+// any value is fine, for fields that are looked at later
+impl TryFrom<&Value> for Value {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(value.clone())
+    }
+}
+
+impl TryFrom<&Value> for bool {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(bool) => Ok(*bool),
+            other => Err(ConvertError::wrong_type("bool", other)),
+        }
+    }
+}
+
+impl TryFrom<&Value> for String {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Str(string) => Ok(string.clone()),
+            other => Err(ConvertError::wrong_type("string", other)),
+        }
+    }
+}
+
+impl<T> TryFrom<&Value> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = ConvertError>,
+{
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let Value::List(list) = value else {
+            return Err(ConvertError::wrong_type("list", value));
+        };
+        list.iter()
+            .enumerate()
+            .map(|(index, item)| T::try_from(item).map_err(|error| error.within_index(index)))
+            .collect()
+    }
+}
+
+impl<T> TryFrom<&Value> for Option<T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = ConvertError>,
+{
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Ok(None),
+            other => T::try_from(other).map(Some),
+        }
+    }
+}
+
+impl<T> TryFrom<&Value> for HashMap<String, T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = ConvertError>,
+{
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let Value::Map(map) = value else {
+            return Err(ConvertError::wrong_type("map", value));
+        };
+        map.iter()
+            .map(|(key, item)| {
+                let item = T::try_from(item).map_err(|error| error.within_key(key))?;
+                Ok((key.clone(), item))
+            })
+            .collect()
+    }
+}
+
+// converts a struct to a map of its fields and back, every field needs its own conversions
+// value_struct!(Range { start, end });
+#[macro_export]
+macro_rules! value_struct {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        impl From<&$name> for $crate::value::Value {
+            fn from(value: &$name) -> Self {
+                $crate::value::Value::from_entries(vec![
+                    $((stringify!($field), $crate::value::Value::from(value.$field.clone())),)*
+                ])
+            }
+        }
+
+        impl TryFrom<&$crate::value::Value> for $name {
+            type Error = $crate::value::ConvertError;
+
+            fn try_from(value: &$crate::value::Value) -> Result<Self, Self::Error> {
+                Ok(Self {
+                    $($field: value.field(stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{ConvertError, ConvertErrorKind};
+    use crate::value::Value;
+
+    #[derive(Debug, PartialEq)]
+    struct Report {
+        day: u8,
+        answers: Vec<i64>,
+        note: Option<String>,
+    }
+
+    value_struct!(Report { day, answers, note });
+
+    #[test]
+    fn test_from_rust() {
+        assert_eq!(Value::from(3u8), Value::Int(3));
+        assert_eq!(Value::from(-3i32), Value::Int(-3));
+        assert_eq!(Value::from(7usize), Value::Int(7));
+        assert_eq!(Value::from(u64::MAX), Value::Hex(u64::MAX));
+        assert_eq!(Value::from(0.5), Value::Float(0.5));
+        assert_eq!(Value::from("hi"), Value::Str("hi".into()));
+        assert_eq!(Value::from(Some(true)), Value::Bool(true));
+        assert_eq!(Value::from(None::<i64>), Value::Null);
+        assert_eq!(
+            Value::from(vec![1, 2]),
+            Value::List(vec![Value::Int(1), Value::Int(2)])
+        );
+        let map = Value::from(HashMap::from([("b", 2), ("a", 1)]));
+        assert_eq!(map.to_string(), r#"{"a": 1, "b": 2}"#);
+    }
+
+    #[test]
+    fn test_into_rust() {
+        assert_eq!(i64::try_from(&Value::Hex(255)), Ok(255));
+        assert_eq!(u8::try_from(&Value::Int(255)), Ok(255));
+        assert_eq!(
+            u8::try_from(&Value::Int(256)),
+            Err(ConvertError::new(ConvertErrorKind::OutOfRange {
+                value: "256".into(),
+                target: "u8"
+            }))
+        );
+        assert_eq!(f64::try_from(&Value::Exponent(1.5, 2)), Ok(150.0));
+        assert_eq!(f64::try_from(&Value::Int(2)), Ok(2.0));
+        assert_eq!(String::try_from(&Value::Str("x".into())), Ok("x".into()));
+        assert_eq!(Option::<bool>::try_from(&Value::Null), Ok(None));
+        assert_eq!(
+            Vec::<i32>::try_from(&Value::parse("[1, 0b10]").unwrap()),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            HashMap::<String, i64>::try_from(&Value::parse("{\"a\": 1}").unwrap()),
+            Ok(HashMap::from([("a".to_string(), 1)]))
+        );
+        assert_eq!(
+            bool::try_from(&Value::Int(1)),
+            Err(ConvertError::new(ConvertErrorKind::WrongType {
+                expected: "bool",
+                found: "int"
+            }))
+        );
+    }
+
+    #[test]
+    fn test_struct() {
+        let report = Report {
+            day: 4,
+            answers: vec![13, 43],
+            note: None,
+        };
+        let value = Value::from(&report);
+        assert_eq!(
+            value.to_string(),
+            r#"{"day": 4, "answers": [13, 43], "note": null}"#
+        );
+        assert_eq!(Report::try_from(&value), Ok(report));
+
+        // a missing optional field is fine, a missing required one isn't
+        let value = Value::parse(r#"{"day": 4, "answers": []}"#).unwrap();
+        assert_eq!(Report::try_from(&value).unwrap().note, None);
+        let value = Value::parse(r#"{"answers": []}"#).unwrap();
+        assert_eq!(
+            Report::try_from(&value).unwrap_err().kind,
+            ConvertErrorKind::MissingKey("day".into())
+        );
+    }
+
+    #[test]
+    fn test_error_path() {
+        let value = Value::parse(r#"{"day": 4, "answers": [1, "two"]}"#).unwrap();
+        let error = Report::try_from(&value).unwrap_err();
+        assert_eq!(error.path, "answers[1]");
+        assert_eq!(
+            error.to_string(),
+            "At 'answers[1]': Expected int, found string"
+        );
+
+        let nested = Value::parse(r#"{"outer": {"inner": [true, 3]}}"#).unwrap();
+        let error = HashMap::<String, HashMap<String, Vec<bool>>>::try_from(&nested).unwrap_err();
+        assert_eq!(error.path, "outer.inner[1]");
+    }
+}
@@ -0,0 +1,375 @@
+use std::fmt::Display;
+
+use super::{Path, Segment, Value, path::resolve_index};
+
+// one change to a value, paths only use keys and indexes so they point at exactly one place
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    // a new map key, or a list item inserted before the index (the length appends)
+    Add { path: Path, value: Value },
+    Remove { path: Path },
+    Replace { path: Path, value: Value },
+}
+
+impl Operation {
+    pub fn path(&self) -> &Path {
+        match self {
+            Operation::Add { path, .. }
+            | Operation::Remove { path }
+            | Operation::Replace { path, .. } => path,
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Add { path, value } => write!(f, "+ {}: {}", path, value),
+            Operation::Remove { path } => write!(f, "- {}", path),
+            Operation::Replace { path, value } => write!(f, "~ {}: {}", path, value),
+        }
+    }
+}
+
+impl From<&Operation> for Value {
+    fn from(value: &Operation) -> Self {
+        let (op, path, replacement) = match value {
+            Operation::Add { path, value } => ("add", path, Some(value)),
+            Operation::Remove { path } => ("remove", path, None),
+            Operation::Replace { path, value } => ("replace", path, Some(value)),
+        };
+        let mut entries = vec![
+            ("op", Value::Str(op.into())),
+            ("path", Value::Str(path.to_string())),
+        ];
+        entries.extend(replacement.map(|replacement| ("value", replacement.clone())));
+        Value::from_entries(entries)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchErrorKind {
+    // wildcards and filters could match anything, so they can't be patched
+    NotConcrete,
+    Missing,
+    AlreadyExists,
+}
+
+impl Display for PatchErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchErrorKind::NotConcrete => write!(f, "Path must only use keys and indexes"),
+            PatchErrorKind::Missing => write!(f, "Nothing there to change"),
+            PatchErrorKind::AlreadyExists => write!(f, "Something is already there"),
+        }
+    }
+}
+
+// operation is the position in the list of the one that failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchError {
+    pub operation: usize,
+    pub path: Path,
+    pub kind: PatchErrorKind,
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Operation {} at '{}': {}",
+            self.operation, self.path, self.kind
+        )
+    }
+}
+
+fn child(mut path: Path, segment: Segment) -> Path {
+    path.segments.push(segment);
+    path
+}
+
+fn diff_into(before: &Value, after: &Value, path: Path, operations: &mut Vec<Operation>) {
+    match (before, after) {
+        (Value::Map(before), Value::Map(after)) => {
+            for (key, old) in before.iter() {
+                let path = child(path.clone(), Segment::Key(key.clone()));
+                match after.get(key) {
+                    Some(new) => diff_into(old, new, path, operations),
+                    None => operations.push(Operation::Remove { path }),
+                }
+            }
+            for (key, new) in after.iter().filter(|(key, _)| !before.contains_key(key)) {
+                operations.push(Operation::Add {
+                    path: child(path.clone(), Segment::Key(key.clone())),
+                    value: new.clone(),
+                });
+            }
+        }
+        (Value::List(before), Value::List(after)) => {
+            for (index, (old, new)) in before.iter().zip(after).enumerate() {
+                diff_into(
+                    old,
+                    new,
+                    child(path.clone(), Segment::Index(index as i64)),
+                    operations,
+                );
+            }
+            for (index, new) in after.iter().enumerate().skip(before.len()) {
+                operations.push(Operation::Add {
+                    path: child(path.clone(), Segment::Index(index as i64)),
+                    value: new.clone(),
+                });
+            }
+            // from the back, so earlier removals don't shift the later indexes
+            for index in (after.len()..before.len()).rev() {
+                operations.push(Operation::Remove {
+                    path: child(path.clone(), Segment::Index(index as i64)),
+                });
+            }
+        }
+        // by what's written rather than by value, so swapping 0xFF for 255 isn't lost
+        (before, after) if before.to_string() != after.to_string() => {
+            operations.push(Operation::Replace {
+                path,
+                value: after.clone(),
+            })
+        }
+        _ => {}
+    }
+}
+
+// follows keys and indexes down to one value
+fn locate<'a>(value: &'a mut Value, segments: &[Segment]) -> Result<&'a mut Value, PatchErrorKind> {
+    let Some((segment, rest)) = segments.split_first() else {
+        return Ok(value);
+    };
+    let next = match (segment, value) {
+        (Segment::Wildcard | Segment::Filter(_), _) => return Err(PatchErrorKind::NotConcrete),
+        (Segment::Key(key), Value::Map(map)) => map.get_mut(key),
+        (Segment::Index(index), Value::List(list)) => {
+            resolve_index(*index, list.len()).map(|index| &mut list[index])
+        }
+        _ => None,
+    };
+    locate(next.ok_or(PatchErrorKind::Missing)?, rest)
+}
+
+fn apply_one(value: &mut Value, operation: &Operation) -> Result<(), PatchErrorKind> {
+    let segments = &operation.path().segments;
+    let Some((last, parent)) = segments.split_last() else {
+        // the whole value is being swapped out
+        return match operation {
+            Operation::Add { value: new, .. } | Operation::Replace { value: new, .. } => {
+                *value = new.clone();
+                Ok(())
+            }
+            Operation::Remove { .. } => Err(PatchErrorKind::Missing),
+        };
+    };
+    let parent = locate(value, parent)?;
+    match (operation, last, parent) {
+        (_, Segment::Wildcard | Segment::Filter(_), _) => Err(PatchErrorKind::NotConcrete),
+        (Operation::Add { value, .. }, Segment::Key(key), Value::Map(map)) => {
+            if map.contains_key(key) {
+                return Err(PatchErrorKind::AlreadyExists);
+            }
+            map.insert(key.clone(), value.clone());
+            Ok(())
+        }
+        (Operation::Add { value, .. }, Segment::Index(index), Value::List(list)) => {
+            // one past the end is allowed, to append
+            let index = resolve_index(*index, list.len() + 1).ok_or(PatchErrorKind::Missing)?;
+            list.insert(index, value.clone());
+            Ok(())
+        }
+        (Operation::Remove { .. }, Segment::Key(key), Value::Map(map)) => {
+            map.remove(key).map(|_| ()).ok_or(PatchErrorKind::Missing)
+        }
+        (Operation::Remove { .. }, Segment::Index(index), Value::List(list)) => {
+            let index = resolve_index(*index, list.len()).ok_or(PatchErrorKind::Missing)?;
+            list.remove(index);
+            Ok(())
+        }
+        (Operation::Replace { value, .. }, last, parent) => {
+            *locate(parent, std::slice::from_ref(last))? = value.clone();
+            Ok(())
+        }
+        _ => Err(PatchErrorKind::Missing),
+    }
+}
+
+impl Value {
+    // the operations that turn this value into the other one, empty when they're equal
+    pub fn diff(&self, other: &Value) -> Vec<Operation> {
+        let mut operations = vec![];
+        diff_into(self, other, Path::default(), &mut operations);
+        operations
+    }
+
+    // applies every operation in order, if one fails the value is left as it was
+    pub fn apply(&mut self, operations: &[Operation]) -> Result<(), PatchError> {
+        let mut patched = self.clone();
+        for (position, operation) in operations.iter().enumerate() {
+            apply_one(&mut patched, operation).map_err(|kind| PatchError {
+                operation: position,
+                path: operation.path().clone(),
+                kind,
+            })?;
+        }
+        *self = patched;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Operation, PatchError, PatchErrorKind};
+    use crate::value::{Path, Value};
+
+    fn value(input: &str) -> Value {
+        Value::parse(input).expect("Should be a valid value")
+    }
+
+    fn path(input: &str) -> Path {
+        Path::parse(input).expect("Should be a valid path")
+    }
+
+    fn round_trip(before: &str, after: &str) -> Vec<String> {
+        let (before, after) = (value(before), value(after));
+        let operations = before.diff(&after);
+        let mut patched = before.clone();
+        patched.apply(&operations).unwrap();
+        assert_eq!(patched.to_string(), after.to_string());
+        operations.iter().map(Operation::to_string).collect()
+    }
+
+    #[test]
+    fn test_diff_equal() {
+        assert!(round_trip(r#"{"a": [1, {"b": null}]}"#, r#"{"a": [1, {"b": null}]}"#).is_empty());
+    }
+
+    #[test]
+    fn test_diff_maps() {
+        assert_eq!(
+            round_trip(
+                r#"{"keep": 1, "gone": 2, "inner": {"x": 1, "y": 2}}"#,
+                r#"{"keep": 1, "inner": {"x": 3, "y": 2, "z": []}, "new key": true}"#,
+            ),
+            vec![
+                "- gone",
+                "~ inner.x: 3",
+                "+ inner.z: []",
+                "+ [\"new key\"]: true",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lists() {
+        assert_eq!(
+            round_trip("[1, 2, 3, 4]", "[1, 5]"),
+            vec!["~ [1]: 5", "- [3]", "- [2]"]
+        );
+        assert_eq!(
+            round_trip("[[1], {\"a\": [true]}]", "[[1, 2], {\"a\": [false]}, null]"),
+            vec!["+ [0][1]: 2", "~ [1].a[0]: false", "+ [2]: null"]
+        );
+    }
+
+    #[test]
+    fn test_diff_types() {
+        assert_eq!(round_trip("[1]", "{\"a\": 1}"), vec!["~ : {\"a\": 1}"]);
+        assert_eq!(
+            round_trip("{\"a\": [1]}", "{\"a\": \"1\"}"),
+            vec!["~ a: \"1\""]
+        );
+    }
+
+    #[test]
+    fn test_diff_radix() {
+        assert_eq!(
+            round_trip("[0xFF, 1e1]", "[255, 10]"),
+            vec!["~ [0]: 255", "~ [1]: 10"]
+        );
+        assert_eq!(
+            round_trip("{\"a\": 0b11}", "{\"a\": 0o3}"),
+            vec!["~ a: 0o3"]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut days = value(r#"{"days": [{"day": 1}, {"day": 3}]}"#);
+        days.apply(&[
+            Operation::Add {
+                path: path("days[1]"),
+                value: value(r#"{"day": 2}"#),
+            },
+            Operation::Replace {
+                path: path("days[-1].day"),
+                value: Value::Int(4),
+            },
+            Operation::Add {
+                path: path("days[0].name"),
+                value: Value::Str("dial".into()),
+            },
+        ])
+        .unwrap();
+        assert_eq!(
+            days,
+            value(r#"{"days": [{"day": 1, "name": "dial"}, {"day": 2}, {"day": 4}]}"#)
+        );
+    }
+
+    #[test]
+    fn test_apply_errors() {
+        let original = value(r#"{"a": [1, 2]}"#);
+        let mut patched = original.clone();
+        let error = patched
+            .apply(&[
+                Operation::Remove { path: path("a[0]") },
+                Operation::Remove { path: path("a[5]") },
+            ])
+            .unwrap_err();
+        assert_eq!(
+            error,
+            PatchError {
+                operation: 1,
+                path: path("a[5]"),
+                kind: PatchErrorKind::Missing
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Operation 1 at 'a[5]': Nothing there to change"
+        );
+        // nothing changes when any operation fails
+        assert_eq!(patched, original);
+
+        let add = Operation::Add {
+            path: path("a"),
+            value: Value::Null,
+        };
+        assert_eq!(
+            patched.apply(&[add]).unwrap_err().kind,
+            PatchErrorKind::AlreadyExists
+        );
+        let remove = Operation::Remove { path: path("a.*") };
+        assert_eq!(
+            patched.apply(&[remove]).unwrap_err().kind,
+            PatchErrorKind::NotConcrete
+        );
+    }
+
+    #[test]
+    fn test_operation_value() {
+        let operation = Operation::Replace {
+            path: path("a[0]"),
+            value: Value::Int(1),
+        };
+        assert_eq!(
+            Value::from(&operation).to_string(),
+            r#"{"op": "replace", "path": "a[0]", "value": 1}"#
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::{Display, Write};
+
+// a string in double quotes, escaped so the parser reads back exactly the same thing
+pub struct Quoted<'a> {
+    pub value: &'a str,
+    // escape everything outside of ascii too, for output that has to stay 7 bit clean
+    pub ascii: bool,
+}
+
+impl<'a> Quoted<'a> {
+    pub fn new(value: &'a str) -> Self {
+        Self {
+            value,
+            ascii: false,
+        }
+    }
+}
+
+fn write_unicode(f: &mut std::fmt::Formatter<'_>, char: char) -> std::fmt::Result {
+    // \u escapes are utf-16, so anything outside the basic plane becomes a surrogate pair
+    let mut units = [0; 2];
+    for unit in char.encode_utf16(&mut units) {
+        write!(f, "\\u{:04x}", unit)?;
+    }
+    Ok(())
+}
+
+impl Display for Quoted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+        for char in self.value.chars() {
+            match char {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                control if control.is_control() => write_unicode(f, control)?,
+                other if self.ascii && !other.is_ascii() => write_unicode(f, other)?,
+                other => f.write_char(other)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Quoted;
+
+    #[test]
+    fn test_quoted() {
+        assert_eq!(Quoted::new("plain").to_string(), "\"plain\"");
+        assert_eq!(
+            Quoted::new("say \"hi\"\\\n\r\t\u{1}\u{7f}").to_string(),
+            r#""say \"hi\"\\\n\r\t\u0001\u007f""#
+        );
+        assert_eq!(Quoted::new("héllo 😀").to_string(), "\"héllo 😀\"");
+        let ascii = Quoted {
+            value: "héllo 😀",
+            ascii: true,
+        };
+        assert_eq!(ascii.to_string(), r#""h\u00e9llo \ud83d\ude00""#);
+    }
+}
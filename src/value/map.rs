@@ -0,0 +1,333 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+use super::{NormalizeOptions, Value};
+
+// the order a map keeps its keys in, which is the order they're printed in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum KeyOrder {
+    // the order keys were first inserted, so output follows the code that built it
+    #[default]
+    Insertion,
+    // always sorted by key, so output doesn't depend on how it was built
+    Sorted,
+}
+
+// string keys to values, iterating in a predictable order instead of hash order
+#[derive(Clone, Default)]
+pub struct Map {
+    order: KeyOrder,
+    entries: Vec<(String, Value)>,
+    // key to position in entries, only kept for insertion order since sorted can binary search
+    index: HashMap<String, usize>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sorted() -> Self {
+        Self::with_order(KeyOrder::Sorted)
+    }
+
+    pub fn with_order(order: KeyOrder) -> Self {
+        Self {
+            order,
+            ..Self::default()
+        }
+    }
+
+    pub fn order(&self) -> KeyOrder {
+        self.order
+    }
+
+    // switching to sorted sorts the keys now, switching back keeps whatever order they are in
+    pub fn set_order(&mut self, order: KeyOrder) {
+        self.order = order;
+        match order {
+            KeyOrder::Sorted => {
+                self.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                self.index.clear();
+            }
+            KeyOrder::Insertion => self.reindex(0),
+        }
+    }
+
+    fn reindex(&mut self, from: usize) {
+        for (position, (key, _)) in self.entries.iter().enumerate().skip(from) {
+            self.index.insert(key.clone(), position);
+        }
+    }
+
+    // where the key is, or where it would go if it isn't there
+    fn find(&self, key: &str) -> Result<usize, usize> {
+        match self.order {
+            KeyOrder::Sorted => self
+                .entries
+                .binary_search_by(|(other, _)| other.as_str().cmp(key)),
+            KeyOrder::Insertion => self.index.get(key).copied().ok_or(self.entries.len()),
+        }
+    }
+
+    // replacing a key keeps its position, returning the value it had
+    pub fn insert(&mut self, key: impl Into<String>, value: Value) -> Option<Value> {
+        let key = key.into();
+        match self.find(&key) {
+            Ok(position) => Some(std::mem::replace(&mut self.entries[position].1, value)),
+            Err(position) => {
+                if self.order == KeyOrder::Insertion {
+                    self.index.insert(key.clone(), position);
+                }
+                self.entries.insert(position, (key, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let position = self.find(key).ok()?;
+        let (_, value) = self.entries.remove(position);
+        if self.order == KeyOrder::Insertion {
+            self.index.remove(key);
+            self.reindex(position);
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.find(key)
+            .ok()
+            .map(|position| &self.entries[position].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        let position = self.find(key).ok()?;
+        Some(&mut self.entries[position].1)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&String, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = (&String, &mut Value)> {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &String> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &Value> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
+
+// maps are equal when they hold the same entries, whatever order those are in
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Eq for Map {}
+
+impl Map {
+    fn sorted_entries(&self) -> Vec<(&String, &Value)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        entries
+    }
+}
+
+// like equality, ordering and hashing go by the entries sorted by key
+impl Ord for Map {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted_entries().cmp(&other.sorted_entries())
+    }
+}
+
+impl PartialOrd for Map {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_entries().hash(state);
+    }
+}
+
+impl std::fmt::Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Value)> for Map {
+    fn from_iter<T: IntoIterator<Item = (K, Value)>>(iter: T) -> Self {
+        let mut map = Map::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, Value);
+    type IntoIter = std::vec::IntoIter<(String, Value)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl Value {
+    // sorts the keys of every map inside, so equal values always print the same
+    pub fn sort_keys(&mut self) {
+        match self {
+            Value::List(list) => list.iter_mut().for_each(Value::sort_keys),
+            Value::Map(map) => {
+                map.set_order(KeyOrder::Sorted);
+                map.iter_mut().for_each(|(_, value)| value.sort_keys());
+            }
+            _ => {}
+        }
+    }
+
+    // the compact form with every map's keys sorted and every number normalized,
+    // byte for byte the same for equal values
+    pub fn to_canonical(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.normalize(&NormalizeOptions::default());
+        canonical.sort_keys();
+        canonical.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{KeyOrder, Map};
+    use crate::value::Value;
+
+    fn keys(map: &Map) -> Vec<&str> {
+        map.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map: Map = [
+            ("b", Value::Int(1)),
+            ("a", Value::Int(2)),
+            ("c", Value::Int(3)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(keys(&map), vec!["b", "a", "c"]);
+        assert_eq!(map.insert("a", Value::Int(4)), Some(Value::Int(2)));
+        assert_eq!(keys(&map), vec!["b", "a", "c"]);
+        assert_eq!(map.remove("b"), Some(Value::Int(1)));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(map.get("c"), Some(&Value::Int(3)));
+        map.insert("b", Value::Null);
+        assert_eq!(keys(&map), vec!["a", "c", "b"]);
+        assert_eq!(map.get("a"), Some(&Value::Int(4)));
+    }
+
+    #[test]
+    fn test_sorted_order() {
+        let mut map = Map::sorted();
+        for key in ["m", "z", "a"] {
+            map.insert(key, Value::Bool(true));
+        }
+        assert_eq!(keys(&map), vec!["a", "m", "z"]);
+        assert!(map.remove("m").is_some());
+        assert!(map.contains_key("z"));
+        assert!(!map.contains_key("m"));
+
+        let mut map: Map = [("y", Value::Null), ("x", Value::Null)]
+            .into_iter()
+            .collect();
+        map.set_order(KeyOrder::Sorted);
+        assert_eq!(keys(&map), vec!["x", "y"]);
+        map.set_order(KeyOrder::Insertion);
+        map.insert("a", Value::Null);
+        assert_eq!(keys(&map), vec!["x", "y", "a"]);
+        assert!(map.get("y").is_some());
+    }
+
+    #[test]
+    fn test_equality_ignores_order() {
+        let forwards: Map = [("a", Value::Int(1)), ("b", Value::Int(2))]
+            .into_iter()
+            .collect();
+        let mut backwards = Map::sorted();
+        backwards.insert("b", Value::Int(2));
+        backwards.insert("a", Value::Int(1));
+        assert_eq!(forwards, backwards);
+        backwards.insert("a", Value::Int(3));
+        assert_ne!(forwards, backwards);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let first = Value::from_entries(vec![
+            (
+                "key2",
+                Value::List(vec![Value::from_entries(vec![
+                    ("y", Value::Null),
+                    ("x", Value::Null),
+                ])]),
+            ),
+            ("key1", Value::Int(1)),
+        ]);
+        let second = Value::from_entries(vec![
+            ("key1", Value::Int(1)),
+            (
+                "key2",
+                Value::List(vec![Value::from_entries(vec![
+                    ("x", Value::Null),
+                    ("y", Value::Null),
+                ])]),
+            ),
+        ]);
+        assert_ne!(first.to_string(), second.to_string());
+        assert_eq!(first.to_canonical(), second.to_canonical());
+        assert_eq!(
+            first.to_canonical(),
+            r#"{"key1": 1, "key2": [{"x": null, "y": null}]}"#
+        );
+    }
+
+    #[test]
+    fn test_canonical_numbers() {
+        assert_eq!(
+            Value::Hex(255).to_canonical(),
+            Value::Int(255).to_canonical()
+        );
+        let first = Value::parse(r#"{"b": [1e1, 2.0, +Infinity], "a": 0x1}"#).unwrap();
+        let second = Value::parse(r#"{"a": 1, "b": [10, 0b10, Infinity]}"#).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.to_canonical(), second.to_canonical());
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+mod binary;
+mod convert;
+mod diff;
+mod escape;
+mod map;
+mod number;
+mod parse;
+mod path;
+mod pretty;
+mod schema;
+mod stream;
+
+pub use binary::{DecodeError, DecodeErrorKind};
+pub use convert::{ConvertError, ConvertErrorKind};
+pub use diff::{Operation, PatchError, PatchErrorKind};
+use escape::Quoted;
+pub use map::{KeyOrder, Map};
+pub use number::{NormalizeOptions, Number};
+pub use parse::{ParseValueError, ParseValueErrorKind};
+pub use path::{Comparison, Filter, Path, PathError, PathErrorKind, Segment};
+pub use pretty::PrettyOptions;
+pub use schema::{Field, Schema, Violation, ViolationKind};
+pub use stream::{StreamError, ValueWriter};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sign {
+    Positive,
+    Negative,
+    None,
+}
+
+// equality, ordering and hashing are by meaning rather than spelling, see number.rs
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i64),
+    Str(String),
+    Float(f64),
+    NaN,
+    Inf(Sign),
+    Exponent(f64, i32),
+    Hex(u64),
+    Oct(u64),
+    Bin(u64),
+    Bool(bool),
+    Null,
+    List(Vec<Value>),
+    Map(Map),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Str(s) => write!(f, "{}", Quoted::new(s)),
+            // always with a point, so it doesn't read back as an int
+            Value::Float(fl) if fl.is_finite() && fl.fract() == 0.0 => write!(f, "{:.1}", fl),
+            Value::Float(fl) if fl.is_nan() => write!(f, "NaN"),
+            Value::Float(fl) if fl.is_infinite() => {
+                write!(f, "{}Infinity", if *fl < 0.0 { "-" } else { "" })
+            }
+            Value::Float(fl) => write!(f, "{}", fl),
+            Value::NaN => write!(f, "NaN"),
+            Value::Inf(sign) => match sign {
+                Sign::Positive => write!(f, "Infinity"),
+                Sign::Negative => write!(f, "-Infinity"),
+                Sign::None => write!(f, "Infinity"),
+            },
+            // NaNe2 wouldn't parse back
+            Value::Exponent(base, _) if !base.is_finite() => write!(f, "{}", Value::Float(*base)),
+            Value::Exponent(base, exp) => write!(f, "{}e{}", base, exp),
+            Value::Hex(h) => write!(f, "0x{:X}", h),
+            Value::Oct(o) => write!(f, "0o{:o}", o),
+            Value::Bin(b) => write!(f, "0b{:b}", b),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Null => write!(f, "null"),
+            Value::List(lst) => {
+                write!(f, "[")?;
+                for (index, value) in lst.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(map) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in map.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", Quoted::new(key), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl Value {
+    // convenience for building a map out of string keys
+    pub fn from_entries(entries: Vec<(&str, Value)>) -> Value {
+        Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+}
+
+// strict JSON has no NaN, infinities or radix literals, so those get flattened
+pub struct Json<'a>(pub &'a Value);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Str(s) => write!(f, "{}", Quoted::new(s)),
+            Value::Float(fl) if fl.is_finite() => write!(f, "{}", fl),
+            Value::Exponent(base, exp) if base.is_finite() => write!(f, "{}e{}", base, exp),
+            Value::Float(_) | Value::Exponent(..) | Value::NaN | Value::Inf(_) => write!(f, "null"),
+            Value::Hex(n) | Value::Oct(n) | Value::Bin(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Null => write!(f, "null"),
+            Value::List(lst) => {
+                write!(f, "[")?;
+                for (index, value) in lst.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", Json(value))?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(map) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in map.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", Quoted::new(key), Json(value))?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl Value {
+    pub fn to_json(&self) -> String {
+        Json(self).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Sign, Value};
+
+    #[test]
+    fn test_json_scalars() {
+        assert_eq!(Value::Int(-42).to_json(), "-42");
+        assert_eq!(Value::Float(1.5).to_json(), "1.5");
+        assert_eq!(Value::Float(f64::NAN).to_json(), "null");
+        assert_eq!(Value::NaN.to_json(), "null");
+        assert_eq!(Value::Inf(Sign::Negative).to_json(), "null");
+        assert_eq!(Value::Exponent(1.5, -3).to_json(), "1.5e-3");
+        assert_eq!(Value::Hex(255).to_json(), "255");
+        assert_eq!(Value::Oct(8).to_json(), "8");
+        assert_eq!(Value::Bin(5).to_json(), "5");
+        assert_eq!(Value::Bool(true).to_json(), "true");
+        assert_eq!(Value::Null.to_json(), "null");
+    }
+
+    #[test]
+    fn test_display_infinity() {
+        // an explicit plus sign isn't written back out
+        assert_eq!(Value::Inf(Sign::Positive).to_string(), "Infinity");
+        assert_eq!(Value::Inf(Sign::None).to_string(), "Infinity");
+        assert_eq!(Value::Inf(Sign::Negative).to_string(), "-Infinity");
+    }
+
+    #[test]
+    fn test_display_non_finite_exponent() {
+        assert_eq!(Value::Exponent(f64::NAN, 2).to_string(), "NaN");
+        assert_eq!(
+            Value::Exponent(f64::NEG_INFINITY, 2).to_string(),
+            "-Infinity"
+        );
+        for value in [
+            Value::Exponent(f64::NAN, 2),
+            Value::Exponent(f64::INFINITY, -1),
+        ] {
+            assert_eq!(Value::parse(&value.to_string()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_json_strings() {
+        assert_eq!(
+            Value::Str("say \"hi\"\\\n\u{1}".into()).to_json(),
+            "\"say \\\"hi\\\"\\\\\\n\\u0001\""
+        );
+        assert_eq!(Value::Str("héllo".into()).to_json(), "\"héllo\"");
+    }
+
+    #[test]
+    fn test_display_escapes() {
+        let value = Value::from_entries(vec![("new\nline", Value::Str("\"quoted\"".into()))]);
+        assert_eq!(value.to_string(), r#"{"new\nline": "\"quoted\""}"#);
+        assert_eq!(Value::parse(&value.to_string()), Ok(value));
+    }
+
+    #[test]
+    fn test_json_nested() {
+        let value = Value::List(vec![
+            Value::Int(1),
+            Value::from_entries(vec![("a", Value::List(vec![]))]),
+            Value::Map(Default::default()),
+        ]);
+        assert_eq!(value.to_json(), "[1,{\"a\":[]},{}]");
+    }
+}
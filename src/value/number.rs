@@ -0,0 +1,338 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use super::{Sign, Value};
+
+// every numeric variant boils down to one of these, integers are kept exact
+#[derive(Debug, Clone, Copy)]
+pub enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Value {
+    // exponents are read the way the literal would be, so 1e400 is infinity and 3e-1 is 0.3
+    pub fn as_number(&self) -> Option<Number> {
+        match self {
+            Value::Int(int) => Some(Number::Integer(i128::from(*int))),
+            Value::Hex(n) | Value::Oct(n) | Value::Bin(n) => Some(Number::Integer(i128::from(*n))),
+            Value::Float(float) => Some(Number::Float(*float)),
+            Value::Exponent(base, exponent) => {
+                Some(Number::Float(exponent_to_f64(*base, *exponent)))
+            }
+            Value::NaN => Some(Number::Float(f64::NAN)),
+            Value::Inf(Sign::Negative) => Some(Number::Float(f64::NEG_INFINITY)),
+            Value::Inf(_) => Some(Number::Float(f64::INFINITY)),
+            _ => None,
+        }
+    }
+}
+
+// multiplying by a power of ten would round twice
+fn exponent_to_f64(base: f64, exponent: i32) -> f64 {
+    if !base.is_finite() {
+        return base;
+    }
+    format!("{}e{}", base, exponent)
+        .parse()
+        .expect("A finite float with an exponent always parses")
+}
+
+impl Number {
+    pub fn is_nan(&self) -> bool {
+        matches!(self, Number::Float(float) if float.is_nan())
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(int) => *int as f64,
+            Number::Float(float) => *float,
+        }
+    }
+
+    // the integer a float is exactly equal to, if there is one
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            Number::Integer(int) => Some(*int),
+            Number::Float(float)
+                if float.is_finite() && float.fract() == 0.0 && float.abs() < 2f64.powi(127) =>
+            {
+                Some(*float as i128)
+            }
+            Number::Float(_) => None,
+        }
+    }
+
+    // exact even where an int has more digits than a float can hold, NaN sorts above everything
+    pub fn total_cmp(&self, other: &Number) -> Ordering {
+        match (self, other) {
+            (Number::Integer(left), Number::Integer(right)) => left.cmp(right),
+            (Number::Integer(int), Number::Float(float)) => compare_int_float(*int, *float),
+            (Number::Float(float), Number::Integer(int)) => {
+                compare_int_float(*int, *float).reverse()
+            }
+            (Number::Float(left), Number::Float(right)) => match (left.is_nan(), right.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                // -0.0 and 0.0 come out equal here
+                (false, false) => left.partial_cmp(right).expect("Neither side is NaN"),
+            },
+        }
+    }
+}
+
+fn compare_int_float(int: i128, float: f64) -> Ordering {
+    if float.is_nan() {
+        return Ordering::Less;
+    }
+    if float.is_infinite() {
+        return if float > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+    }
+    // whole parts first, then the fraction decides a tie
+    let whole = float.trunc();
+    int.cmp(&(whole as i128)).then_with(|| {
+        0.0.partial_cmp(&(float - whole))
+            .expect("Finite floats compare")
+    })
+}
+
+impl Hash for Number {
+    // anything equal to an integer hashes as that integer, so 255, 0xFF and 255.0 agree
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match (self.as_integer(), self) {
+            (Some(int), _) => (0u8, int).hash(state),
+            (None, number) if number.is_nan() => 1u8.hash(state),
+            (None, number) => (2u8, number.as_f64().to_bits()).hash(state),
+        }
+    }
+}
+
+// which group a value sorts into, before its contents are looked at
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Str(_) => 3,
+        Value::List(_) => 4,
+        Value::Map(_) => 5,
+        _ => 2,
+    }
+}
+
+// null < bools < numbers < strings < lists < maps, numbers compare by what they're worth
+// whatever they're written as, and every NaN is equal to every other and above all numbers
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Bool(left), Value::Bool(right)) => left.cmp(right),
+            (Value::Str(left), Value::Str(right)) => left.cmp(right),
+            (Value::List(left), Value::List(right)) => left.cmp(right),
+            (Value::Map(left), Value::Map(right)) => left.cmp(right),
+            (left, right) => match (left.as_number(), right.as_number()) {
+                (Some(left), Some(right)) => left.total_cmp(&right),
+                _ => rank(left).cmp(&rank(right)),
+            },
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        rank(self).hash(state);
+        match self {
+            Value::Str(string) => string.hash(state),
+            Value::Bool(bool) => bool.hash(state),
+            Value::Null => {}
+            Value::List(list) => list.hash(state),
+            Value::Map(map) => map.hash(state),
+            number => number.as_number().hash(state),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NormalizeOptions {
+    // leaves hex, octal and binary as they were written instead of making them ints
+    pub keep_radix: bool,
+}
+
+impl Value {
+    // rewrites every number in its simplest form: whole numbers become ints (or hex when too
+    // big for one), NaN and infinities get their own variants and everything else is a float
+    pub fn normalize(&mut self, options: &NormalizeOptions) {
+        let normalized = match self {
+            Value::List(list) => {
+                list.iter_mut().for_each(|item| item.normalize(options));
+                return;
+            }
+            Value::Map(map) => {
+                map.iter_mut().for_each(|(_, item)| item.normalize(options));
+                return;
+            }
+            Value::Hex(_) | Value::Oct(_) | Value::Bin(_) if options.keep_radix => return,
+            Value::Hex(n) | Value::Oct(n) | Value::Bin(n) => match i64::try_from(*n) {
+                Ok(int) => Value::Int(int),
+                Err(_) => Value::Hex(*n),
+            },
+            Value::Float(_) | Value::Exponent(..) | Value::NaN | Value::Inf(_) => {
+                let Some(number) = self.as_number() else {
+                    return;
+                };
+                let float = number.as_f64();
+                match number.as_integer().map(i64::try_from) {
+                    Some(Ok(int)) => Value::Int(int),
+                    _ if float.is_nan() => Value::NaN,
+                    _ if float == f64::INFINITY => Value::Inf(Sign::None),
+                    _ if float == f64::NEG_INFINITY => Value::Inf(Sign::Negative),
+                    _ => Value::Float(float),
+                }
+            }
+            _ => return,
+        };
+        *self = normalized;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::NormalizeOptions;
+    use crate::value::{Sign, Value};
+
+    #[test]
+    fn test_numeric_equality() {
+        assert_eq!(Value::Hex(255), Value::Int(255));
+        assert_eq!(Value::Bin(5), Value::Oct(5));
+        assert_eq!(Value::Float(2.0), Value::Int(2));
+        assert_eq!(Value::Exponent(1.5, 2), Value::Int(150));
+        assert_eq!(Value::Float(-0.0), Value::Int(0));
+        assert_eq!(Value::Inf(Sign::None), Value::Inf(Sign::Positive));
+        assert_eq!(Value::Float(f64::NEG_INFINITY), Value::Inf(Sign::Negative));
+        assert_eq!(Value::Float(f64::NAN), Value::NaN);
+        assert_ne!(Value::Float(2.5), Value::Int(2));
+        assert_ne!(Value::Int(1), Value::Bool(true));
+        assert_ne!(Value::Str("1".into()), Value::Int(1));
+        // bigger than a float can tell apart
+        assert_ne!(Value::Int(i64::MAX), Value::Int(i64::MAX - 1));
+        assert_ne!(
+            Value::Float(9007199254740992.0),
+            Value::Int(9007199254740993)
+        );
+        assert_eq!(
+            Value::parse("[0x10, {\"a\": 1e1}]").unwrap(),
+            Value::parse("[16, {\"a\": 10}]").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut values = vec![
+            Value::NaN,
+            Value::Str("a".into()),
+            Value::Inf(Sign::None),
+            Value::Float(2.5),
+            Value::Hex(2),
+            Value::Null,
+            Value::List(vec![]),
+            Value::Bool(true),
+            Value::Inf(Sign::Negative),
+            Value::Exponent(-1.0, 3),
+            Value::Bool(false),
+            Value::from_entries(vec![]),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                Value::Null,
+                Value::Bool(false),
+                Value::Bool(true),
+                Value::Inf(Sign::Negative),
+                Value::Int(-1000),
+                Value::Int(2),
+                Value::Float(2.5),
+                Value::Inf(Sign::Positive),
+                Value::NaN,
+                Value::Str("a".into()),
+                Value::List(vec![]),
+                Value::from_entries(vec![]),
+            ]
+        );
+        assert!(Value::Int(3) < Value::Float(3.5));
+        assert!(Value::Float(-3.5) < Value::Int(-3));
+        assert!(Value::Hex(u64::MAX) > Value::Int(i64::MAX));
+        assert!(Value::parse("[1, 2]").unwrap() < Value::parse("[1, 2, 0]").unwrap());
+    }
+
+    #[test]
+    fn test_hash_matches_equality() {
+        let values: HashSet<Value> = [
+            Value::Int(255),
+            Value::Hex(255),
+            Value::Float(255.0),
+            Value::Exponent(25.5, 1),
+            Value::NaN,
+            Value::Float(f64::NAN),
+            Value::Float(0.5),
+            Value::Inf(Sign::None),
+            Value::Inf(Sign::Positive),
+            Value::parse("{\"a\": 1, \"b\": 2}").unwrap(),
+            Value::parse("{\"b\": 2, \"a\": 0x1}").unwrap(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(values.len(), 5);
+    }
+
+    #[test]
+    fn test_exponent_is_exact() {
+        let exponent = Value::parse("3e-1").unwrap();
+        let float = Value::parse("0.3").unwrap();
+        assert_eq!(exponent, float);
+        assert_eq!(Value::Exponent(1.1, 1), Value::Float(11.0));
+        let values: HashSet<Value> = [exponent, float].into_iter().collect();
+        assert_eq!(values.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let original = Value::parse(
+            "[0xFF, 0o17, 0b101, 2.0, 2.5, 1.5e2, 1e400, -Infinity, +Infinity, 0xFFFFFFFFFFFFFFFF]",
+        )
+        .unwrap();
+
+        let mut normalized = original.clone();
+        normalized.normalize(&NormalizeOptions::default());
+        assert_eq!(
+            normalized.to_string(),
+            "[255, 15, 5, 2, 2.5, 150, Infinity, -Infinity, Infinity, 0xFFFFFFFFFFFFFFFF]"
+        );
+        assert_eq!(normalized, original);
+
+        let mut normalized = Value::parse("{\"a\": [0xFF, 1e1, NaN]}").unwrap();
+        normalized.normalize(&NormalizeOptions { keep_radix: true });
+        assert_eq!(normalized.to_string(), "{\"a\": [0xFF, 10, NaN]}");
+    }
+}
@@ -0,0 +1,579 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Map, Sign, Value};
+
+// deeper than this would risk running out of stack
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseValueErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    InvalidNumber(String),
+    InvalidEscape(String),
+    DuplicateKey(String),
+    TrailingCharacters,
+    TooDeep,
+}
+
+impl Display for ParseValueErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseValueErrorKind::UnexpectedEnd => write!(f, "Unexpected end of input"),
+            ParseValueErrorKind::UnexpectedChar(char) => {
+                write!(f, "Unexpected character '{}'", char.escape_debug())
+            }
+            ParseValueErrorKind::InvalidNumber(number) => write!(f, "Invalid number '{}'", number),
+            ParseValueErrorKind::InvalidEscape(escape) => {
+                write!(f, "Invalid escape '\\{}'", escape)
+            }
+            ParseValueErrorKind::DuplicateKey(key) => write!(f, "Duplicate key '{}'", key),
+            ParseValueErrorKind::TrailingCharacters => {
+                write!(f, "Unexpected characters after the value")
+            }
+            ParseValueErrorKind::TooDeep => write!(f, "Nested more than {} deep", MAX_DEPTH),
+        }
+    }
+}
+
+// where it went wrong, line and column both start at 1 and columns count characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseValueError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseValueErrorKind,
+}
+
+impl Display for ParseValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    // byte offset of the next character
+    position: usize,
+    // how many lists and maps are open
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error_at(&self, position: usize, kind: ParseValueErrorKind) -> ParseValueError {
+        let before = &self.input[..position];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        ParseValueError {
+            line,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    fn error(&self, kind: ParseValueErrorKind) -> ParseValueError {
+        self.error_at(self.position, kind)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.position += char.len_utf8();
+        Some(char)
+    }
+
+    // the error for whatever is next, which is either a stray character or nothing at all
+    fn unexpected(&self) -> ParseValueError {
+        match self.peek() {
+            Some(char) => self.error(ParseValueErrorKind::UnexpectedChar(char)),
+            None => self.error(ParseValueErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseValueError> {
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if self.input[self.position..].starts_with(word) {
+            self.position += word.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseValueError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => Ok(Value::Str(self.string()?)),
+            Some('[') => self.list(),
+            Some('{') => self.map(),
+            Some('0'..='9' | '-' | '+') => self.number(),
+            Some(_) => self.keyword(),
+            None => Err(self.error(ParseValueErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn keyword(&mut self) -> Result<Value, ParseValueError> {
+        let keywords = [
+            ("null", Value::Null),
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+            ("NaN", Value::NaN),
+            ("Infinity", Value::Inf(Sign::None)),
+        ];
+        for (word, value) in keywords {
+            if self.eat_word(word) {
+                return Ok(value);
+            }
+        }
+        Err(self.unexpected())
+    }
+
+    fn radix(&mut self, start: usize, radix: u32) -> Result<Value, ParseValueError> {
+        let digits_start = self.position;
+        while self.peek().is_some_and(|char| char.is_ascii_alphanumeric()) {
+            self.bump();
+        }
+        let digits = &self.input[digits_start..self.position];
+        let number = u64::from_str_radix(digits, radix).map_err(|_| {
+            let literal = self.input[start..self.position].to_string();
+            self.error_at(start, ParseValueErrorKind::InvalidNumber(literal))
+        })?;
+        Ok(match radix {
+            16 => Value::Hex(number),
+            8 => Value::Oct(number),
+            _ => Value::Bin(number),
+        })
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.bump();
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Result<Value, ParseValueError> {
+        let start = self.position;
+        let sign = match self.peek() {
+            Some('-') => {
+                self.bump();
+                Sign::Negative
+            }
+            Some('+') => {
+                self.bump();
+                Sign::Positive
+            }
+            _ => Sign::None,
+        };
+        if self.eat_word("Infinity") {
+            return Ok(Value::Inf(sign));
+        }
+
+        // radix literals are unsigned, so they can't follow a sign
+        if matches!(sign, Sign::None) {
+            for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+                if self.eat_word(prefix) {
+                    return self.radix(start, radix);
+                }
+            }
+        }
+
+        let invalid = |parser: &Self| {
+            let literal = parser.input[start..parser.position].to_string();
+            parser.error_at(start, ParseValueErrorKind::InvalidNumber(literal))
+        };
+        if self.digits() == 0 {
+            return Err(self.unexpected());
+        }
+        let mut is_float = false;
+        if self.peek() == Some('.') {
+            self.bump();
+            is_float = true;
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+        }
+        let mantissa_end = self.position;
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.bump();
+            let exponent_start = self.position;
+            if matches!(self.peek(), Some('-' | '+')) {
+                self.bump();
+            }
+            if self.digits() == 0 {
+                return Err(self.unexpected());
+            }
+            let base = self.input[start..mantissa_end]
+                .parse::<f64>()
+                .map_err(|_| invalid(self))?;
+            let exponent = self.input[exponent_start..self.position]
+                .parse::<i32>()
+                .map_err(|_| invalid(self))?;
+            return Ok(Value::Exponent(base, exponent));
+        }
+
+        let literal = &self.input[start..self.position];
+        if is_float {
+            literal.parse().map(Value::Float).map_err(|_| invalid(self))
+        } else {
+            literal.parse().map(Value::Int).map_err(|_| invalid(self))
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<u32, ParseValueError> {
+        let start = self.position;
+        for _ in 0..4 {
+            if !self.peek().is_some_and(|char| char.is_ascii_hexdigit()) {
+                let escape = format!("u{}", &self.input[start..self.position]);
+                return Err(self.error_at(start - 2, ParseValueErrorKind::InvalidEscape(escape)));
+            }
+            self.bump();
+        }
+        Ok(u32::from_str_radix(&self.input[start..self.position], 16).expect("Checked as hex"))
+    }
+
+    // \uXXXX escapes are utf-16, so anything outside the basic plane comes as a pair
+    fn unicode(&mut self, escape_start: usize) -> Result<char, ParseValueError> {
+        let invalid = |parser: &Self| {
+            let escape = parser.input[escape_start + 1..parser.position].to_string();
+            parser.error_at(escape_start, ParseValueErrorKind::InvalidEscape(escape))
+        };
+        let high = self.unicode_escape()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| invalid(self));
+        }
+        if !self.eat_word("\\u") {
+            return Err(invalid(self));
+        }
+        let low = self.unicode_escape()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(invalid(self));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| invalid(self))
+    }
+
+    fn string(&mut self) -> Result<String, ParseValueError> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            let escape_start = self.position;
+            match self.bump() {
+                None => return Err(self.error(ParseValueErrorKind::UnexpectedEnd)),
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let unescaped = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => self.unicode(escape_start)?,
+                        Some(other) => {
+                            return Err(self.error_at(
+                                escape_start,
+                                ParseValueErrorKind::InvalidEscape(other.to_string()),
+                            ));
+                        }
+                        None => return Err(self.error(ParseValueErrorKind::UnexpectedEnd)),
+                    };
+                    string.push(unescaped);
+                }
+                Some(char) => string.push(char),
+            }
+        }
+    }
+
+    // the items between open and close, separated by commas
+    fn items(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<(), ParseValueError>,
+    ) -> Result<(), ParseValueError> {
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.bump();
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.bump();
+                    // the pretty printer can leave a comma after the last item
+                    self.skip_whitespace();
+                    if self.peek() == Some(close) {
+                        self.bump();
+                        return Ok(());
+                    }
+                }
+                Some(char) if char == close => {
+                    self.bump();
+                    return Ok(());
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), ParseValueError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(ParseValueErrorKind::TooDeep));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn list(&mut self) -> Result<Value, ParseValueError> {
+        self.enter()?;
+        self.expect('[')?;
+        let mut list = vec![];
+        self.items(']', |parser| {
+            list.push(parser.value()?);
+            Ok(())
+        })?;
+        self.depth -= 1;
+        Ok(Value::List(list))
+    }
+
+    fn map(&mut self) -> Result<Value, ParseValueError> {
+        self.enter()?;
+        self.expect('{')?;
+        let mut map = Map::new();
+        self.items('}', |parser| {
+            parser.skip_whitespace();
+            let key_start = parser.position;
+            let key = parser.string()?;
+            parser.skip_whitespace();
+            parser.expect(':')?;
+            let value = parser.value()?;
+            if map.contains_key(&key) {
+                return Err(parser.error_at(key_start, ParseValueErrorKind::DuplicateKey(key)));
+            }
+            map.insert(key, value);
+            Ok(())
+        })?;
+        self.depth -= 1;
+        Ok(Value::Map(map))
+    }
+}
+
+// reads one value off the front of input, handing back how many bytes it took
+pub fn parse_prefix(input: &str) -> Result<(Value, usize), ParseValueError> {
+    let mut parser = Parser {
+        input,
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    Ok((value, parser.position))
+}
+
+impl Value {
+    // reads back anything Display writes, whitespace between tokens is ignored
+    pub fn parse(input: &str) -> Result<Value, ParseValueError> {
+        let mut parser = Parser {
+            input,
+            position: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < input.len() {
+            return Err(parser.error(ParseValueErrorKind::TrailingCharacters));
+        }
+        Ok(value)
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Value::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MAX_DEPTH, ParseValueError, ParseValueErrorKind};
+    use crate::value::{Sign, Value};
+
+    fn error(input: &str) -> ParseValueError {
+        Value::parse(input).expect_err("Should fail to parse")
+    }
+
+    #[test]
+    fn test_parse_scalars() {
+        assert_eq!(Value::parse("42"), Ok(Value::Int(42)));
+        assert_eq!(Value::parse("-7"), Ok(Value::Int(-7)));
+        assert_eq!(Value::parse("1.5"), Ok(Value::Float(1.5)));
+        assert_eq!(Value::parse("-0.25"), Ok(Value::Float(-0.25)));
+        assert_eq!(Value::parse("2.5e-3"), Ok(Value::Exponent(2.5, -3)));
+        assert_eq!(Value::parse("1e5"), Ok(Value::Exponent(1.0, 5)));
+        assert_eq!(Value::parse("0xFF"), Ok(Value::Hex(255)));
+        assert_eq!(Value::parse("0o17"), Ok(Value::Oct(15)));
+        assert_eq!(Value::parse("0b101"), Ok(Value::Bin(5)));
+        assert_eq!(Value::parse("true"), Ok(Value::Bool(true)));
+        assert_eq!(Value::parse("false"), Ok(Value::Bool(false)));
+        assert_eq!(Value::parse("null"), Ok(Value::Null));
+        assert_eq!(Value::parse("\"hi\""), Ok(Value::Str("hi".into())));
+    }
+
+    #[test]
+    fn test_parse_special_floats() {
+        assert_eq!(Value::parse("NaN"), Ok(Value::NaN));
+        assert_eq!(Value::parse("Infinity"), Ok(Value::Inf(Sign::None)));
+        assert_eq!(Value::parse("+Infinity"), Ok(Value::Inf(Sign::Positive)));
+        assert_eq!(Value::parse("-Infinity"), Ok(Value::Inf(Sign::Negative)));
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        assert_eq!(
+            Value::parse(r#""a\"b\\c\nd\te\u0001é😀""#),
+            Ok(Value::Str("a\"b\\c\nd\te\u{1}é😀".into()))
+        );
+        assert_eq!(
+            error(r#""\q""#).kind,
+            ParseValueErrorKind::InvalidEscape("q".into())
+        );
+        assert_eq!(
+            error(r#""\ud83d""#).kind,
+            ParseValueErrorKind::InvalidEscape("ud83d".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_nested() {
+        let value = Value::parse("{\"a\": [1, 0x2, {\"b\": null}], \"c\": {}}").unwrap();
+        assert_eq!(
+            value,
+            Value::from_entries(vec![
+                (
+                    "a",
+                    Value::List(vec![
+                        Value::Int(1),
+                        Value::Hex(2),
+                        Value::from_entries(vec![("b", Value::Null)]),
+                    ])
+                ),
+                ("c", Value::from_entries(vec![])),
+            ])
+        );
+        assert_eq!(Value::parse(" [ ] "), Ok(Value::List(vec![])));
+        assert_eq!(Value::parse("[1, ]"), Ok(Value::List(vec![Value::Int(1)])));
+        assert_eq!(error("[,]").kind, ParseValueErrorKind::UnexpectedChar(','));
+        assert_eq!("[1]".parse::<Value>(), Ok(Value::List(vec![Value::Int(1)])));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = vec![
+            Value::Int(i64::MIN),
+            Value::Float(1.0),
+            Value::Float(0.1),
+            Value::Float(-1e20),
+            Value::Exponent(-1.25, 12),
+            Value::Hex(u64::MAX),
+            Value::Oct(8),
+            Value::Bin(0),
+            Value::NaN,
+            Value::Inf(Sign::Positive),
+            Value::Inf(Sign::Negative),
+            Value::Inf(Sign::None),
+            Value::List(vec![
+                Value::Str("x".into()),
+                Value::List(vec![]),
+                Value::from_entries(vec![("k", Value::Bool(false))]),
+            ]),
+        ];
+        for value in values {
+            let parsed = Value::parse(&value.to_string()).unwrap();
+            assert_eq!(parsed.to_string(), value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            error("[1, 2"),
+            ParseValueError {
+                line: 1,
+                column: 6,
+                kind: ParseValueErrorKind::UnexpectedEnd
+            }
+        );
+        assert_eq!(
+            error("{\n  \"a\": 1,\n  \"b\" 2\n}"),
+            ParseValueError {
+                line: 3,
+                column: 7,
+                kind: ParseValueErrorKind::UnexpectedChar('2')
+            }
+        );
+        assert_eq!(
+            error("[\"é\", nope]"),
+            ParseValueError {
+                line: 1,
+                column: 7,
+                kind: ParseValueErrorKind::UnexpectedChar('n')
+            }
+        );
+        assert_eq!(
+            error("99999999999999999999").kind,
+            ParseValueErrorKind::InvalidNumber("99999999999999999999".into())
+        );
+        assert_eq!(
+            error("0xZZ").kind,
+            ParseValueErrorKind::InvalidNumber("0xZZ".into())
+        );
+        assert_eq!(
+            error("{\"a\": 1, \"a\": 2}"),
+            ParseValueError {
+                line: 1,
+                column: 10,
+                kind: ParseValueErrorKind::DuplicateKey("a".into())
+            }
+        );
+        assert_eq!(error("1 2").kind, ParseValueErrorKind::TrailingCharacters);
+        assert_eq!(error("").kind, ParseValueErrorKind::UnexpectedEnd);
+        assert_eq!(error("1.").kind, ParseValueErrorKind::UnexpectedEnd);
+        // deep enough to overflow the stack if it were followed
+        assert_eq!(
+            error(&"[".repeat(200_000)).kind,
+            ParseValueErrorKind::TooDeep
+        );
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Value::parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            error(&nested(MAX_DEPTH + 1)).kind,
+            ParseValueErrorKind::TooDeep
+        );
+    }
+}
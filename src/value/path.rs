@@ -0,0 +1,573 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use super::{ParseValueError, Quoted, Value, parse::parse_prefix};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    const SYMBOLS: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        Comparison::SYMBOLS
+            .iter()
+            .find(|(_, comparison)| comparison == self)
+            .map(|(symbol, _)| *symbol)
+            .expect("Every comparison has a symbol")
+    }
+}
+
+// keeps the children where the relative path finds something, that passes the test if there is one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub path: Path,
+    pub test: Option<(Comparison, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    // negative indexes count back from the end
+    Index(i64),
+    Wildcard,
+    Filter(Filter),
+}
+
+// a route into a value, like 'days.*.parts[0]' or 'days[?(@.day >= 3)]'
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    InvalidIndex(String),
+    InvalidValue(ParseValueError),
+}
+
+impl Display for PathErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathErrorKind::UnexpectedEnd => write!(f, "Unexpected end of path"),
+            PathErrorKind::UnexpectedChar(char) => {
+                write!(f, "Unexpected character '{}'", char.escape_debug())
+            }
+            PathErrorKind::InvalidIndex(index) => write!(f, "Invalid index '{}'", index),
+            PathErrorKind::InvalidValue(error) => write!(f, "Invalid value, {}", error),
+        }
+    }
+}
+
+// columns start at 1 and count characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathError {
+    pub column: usize,
+    pub kind: PathErrorKind,
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: {}", self.column, self.kind)
+    }
+}
+
+struct PathParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+fn is_name_char(char: char) -> bool {
+    char.is_alphanumeric() || char == '_' || char == '-'
+}
+
+impl PathParser<'_> {
+    fn error_at(&self, position: usize, kind: PathErrorKind) -> PathError {
+        PathError {
+            column: self.input[..position].chars().count() + 1,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.position += char.len_utf8();
+        Some(char)
+    }
+
+    fn unexpected(&self) -> PathError {
+        match self.peek() {
+            Some(char) => self.error_at(self.position, PathErrorKind::UnexpectedChar(char)),
+            None => self.error_at(self.position, PathErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PathError> {
+        if self.peek() == Some(expected) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    // a value literal, using the value parser for everything it understands
+    fn literal(&mut self) -> Result<Value, PathError> {
+        let (value, used) = parse_prefix(&self.input[self.position..])
+            .map_err(|error| self.error_at(self.position, PathErrorKind::InvalidValue(error)))?;
+        self.position += used;
+        Ok(value)
+    }
+
+    // after a dot: a bare key or a wildcard
+    fn dotted(&mut self) -> Result<Segment, PathError> {
+        if self.peek() == Some('*') {
+            self.bump();
+            return Ok(Segment::Wildcard);
+        }
+        let start = self.position;
+        while self.peek().is_some_and(is_name_char) {
+            self.bump();
+        }
+        if self.position == start {
+            return Err(self.unexpected());
+        }
+        Ok(Segment::Key(self.input[start..self.position].to_string()))
+    }
+
+    fn filter(&mut self) -> Result<Filter, PathError> {
+        self.expect('(')?;
+        self.skip_whitespace();
+        self.expect('@')?;
+        let path = self.segments(&[' ', ')', '=', '!', '<', '>'])?;
+        self.skip_whitespace();
+        let comparison = Comparison::SYMBOLS
+            .iter()
+            .find(|(symbol, _)| self.input[self.position..].starts_with(symbol));
+        let test = match comparison {
+            Some((symbol, comparison)) => {
+                self.position += symbol.len();
+                self.skip_whitespace();
+                Some((*comparison, self.literal()?))
+            }
+            None => None,
+        };
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(Filter { path, test })
+    }
+
+    // inside square brackets: a wildcard, index, quoted key or filter
+    fn bracketed(&mut self) -> Result<Segment, PathError> {
+        self.expect('[')?;
+        let segment = match self.peek() {
+            Some('*') => {
+                self.bump();
+                Segment::Wildcard
+            }
+            Some('?') => {
+                self.bump();
+                Segment::Filter(self.filter()?)
+            }
+            Some('"') => match self.literal()? {
+                Value::Str(key) => Segment::Key(key),
+                _ => unreachable!("A quote always starts a string"),
+            },
+            Some('-' | '0'..='9') => {
+                let start = self.position;
+                self.bump();
+                while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+                    self.bump();
+                }
+                let index = &self.input[start..self.position];
+                Segment::Index(index.parse().map_err(|_| {
+                    self.error_at(start, PathErrorKind::InvalidIndex(index.to_string()))
+                })?)
+            }
+            _ => return Err(self.unexpected()),
+        };
+        self.expect(']')?;
+        Ok(segment)
+    }
+
+    // segments until the input runs out or one of the stop characters comes up
+    fn segments(&mut self, stop: &[char]) -> Result<Path, PathError> {
+        let mut segments = vec![];
+        loop {
+            match self.peek() {
+                None => break,
+                Some(char) if stop.contains(&char) => break,
+                Some('[') => segments.push(self.bracketed()?),
+                Some('.') => {
+                    self.bump();
+                    segments.push(self.dotted()?);
+                }
+                // the very first key doesn't need a dot in front of it
+                Some(_) if segments.is_empty() && stop.is_empty() => segments.push(self.dotted()?),
+                Some(_) => return Err(self.unexpected()),
+            }
+        }
+        Ok(Path { segments })
+    }
+}
+
+impl Path {
+    // an empty path is the value itself
+    pub fn parse(input: &str) -> Result<Path, PathError> {
+        let mut parser = PathParser { input, position: 0 };
+        parser.segments(&[])
+    }
+}
+
+// written so it parses back to the same path, bare keys where they can be and quoted otherwise
+fn write_segments(
+    f: &mut std::fmt::Formatter<'_>,
+    segments: &[Segment],
+    bare_start: bool,
+) -> std::fmt::Result {
+    for (position, segment) in segments.iter().enumerate() {
+        let dot = if position == 0 && bare_start { "" } else { "." };
+        match segment {
+            Segment::Key(key) if !key.is_empty() && key.chars().all(is_name_char) => {
+                write!(f, "{}{}", dot, key)?
+            }
+            Segment::Key(key) => write!(f, "[{}]", Quoted::new(key))?,
+            Segment::Index(index) => write!(f, "[{}]", index)?,
+            Segment::Wildcard => write!(f, "{}*", dot)?,
+            Segment::Filter(filter) => {
+                write!(f, "[?(@")?;
+                write_segments(f, &filter.path.segments, false)?;
+                if let Some((comparison, value)) = &filter.test {
+                    write!(f, " {} {}", comparison.symbol(), value)?;
+                }
+                write!(f, ")]")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_segments(f, &self.segments, true)
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Path::parse(s)
+    }
+}
+
+// only numbers with numbers and strings with strings have an order, and NaN has none
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Str(left), Value::Str(right)) => Some(left.cmp(right)),
+        _ => {
+            let (left, right) = (left.as_number()?, right.as_number()?);
+            (!left.is_nan() && !right.is_nan()).then(|| left.total_cmp(&right))
+        }
+    }
+}
+
+impl Filter {
+    pub fn matches(&self, value: &Value) -> bool {
+        let found = value.query(&self.path);
+        let Some((comparison, expected)) = &self.test else {
+            return !found.is_empty();
+        };
+        found.iter().any(|found| {
+            let ordering = compare(found, expected);
+            match comparison {
+                Comparison::Equal => *found == expected,
+                Comparison::NotEqual => *found != expected,
+                Comparison::Less => ordering == Some(Ordering::Less),
+                Comparison::LessOrEqual => {
+                    matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+                }
+                Comparison::Greater => ordering == Some(Ordering::Greater),
+                Comparison::GreaterOrEqual => {
+                    matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+                }
+            }
+        })
+    }
+}
+
+pub fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+fn step<'a>(value: &'a Value, segment: &Segment, found: &mut Vec<&'a Value>) {
+    match (segment, value) {
+        (Segment::Key(key), Value::Map(map)) => found.extend(map.get(key)),
+        (Segment::Index(index), Value::List(list)) => {
+            found.extend(resolve_index(*index, list.len()).map(|index| &list[index]))
+        }
+        (Segment::Wildcard, Value::List(list)) => found.extend(list.iter()),
+        (Segment::Wildcard, Value::Map(map)) => found.extend(map.values()),
+        (Segment::Filter(filter), Value::List(list)) => {
+            found.extend(list.iter().filter(|item| filter.matches(item)))
+        }
+        (Segment::Filter(filter), Value::Map(map)) => {
+            found.extend(map.values().filter(|item| filter.matches(item)))
+        }
+        _ => {}
+    }
+}
+
+fn step_mut<'a>(value: &'a mut Value, segment: &Segment, found: &mut Vec<&'a mut Value>) {
+    match (segment, value) {
+        (Segment::Key(key), Value::Map(map)) => found.extend(map.get_mut(key)),
+        (Segment::Index(index), Value::List(list)) => {
+            if let Some(index) = resolve_index(*index, list.len()) {
+                found.push(&mut list[index]);
+            }
+        }
+        (Segment::Wildcard, Value::List(list)) => found.extend(list.iter_mut()),
+        (Segment::Wildcard, Value::Map(map)) => found.extend(map.iter_mut().map(|(_, item)| item)),
+        (Segment::Filter(filter), Value::List(list)) => {
+            found.extend(list.iter_mut().filter(|item| filter.matches(item)))
+        }
+        (Segment::Filter(filter), Value::Map(map)) => found.extend(
+            map.iter_mut()
+                .map(|(_, item)| item)
+                .filter(|item| filter.matches(item)),
+        ),
+        _ => {}
+    }
+}
+
+impl Value {
+    // everything the path leads to, in document order
+    pub fn query(&self, path: &Path) -> Vec<&Value> {
+        let mut current = vec![self];
+        for segment in &path.segments {
+            let mut next = vec![];
+            for value in current {
+                step(value, segment, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+
+    // the same matches as query, but open to being changed in place
+    pub fn query_mut(&mut self, path: &Path) -> Vec<&mut Value> {
+        let mut current = vec![self];
+        for segment in &path.segments {
+            let mut next = vec![];
+            for value in current {
+                step_mut(value, segment, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+
+    // parses the path and queries with it in one go
+    pub fn select(&self, path: &str) -> Result<Vec<&Value>, PathError> {
+        Ok(self.query(&Path::parse(path)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Comparison, Filter, Path, PathError, PathErrorKind, Segment};
+    use crate::value::Value;
+
+    fn days() -> Value {
+        Value::parse(
+            r#"{
+                "days": [
+                    {"day": 1, "part1": 1132, "tags": ["dial"]},
+                    {"day": 2, "part1": null},
+                    {"day": 3, "part1": 357, "name": "lobby"}
+                ],
+                "key2": [10, 20, 30],
+                "odd key": true
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn select<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+        value.select(path).expect("Should be a valid path")
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(Path::parse(""), Ok(Path::default()));
+        assert_eq!(
+            Path::parse("days[-1].*[\"a.b\"]"),
+            Ok(Path {
+                segments: vec![
+                    Segment::Key("days".into()),
+                    Segment::Index(-1),
+                    Segment::Wildcard,
+                    Segment::Key("a.b".into()),
+                ]
+            })
+        );
+        assert_eq!(
+            Path::parse("[?(@.day >= 2)]"),
+            Ok(Path {
+                segments: vec![Segment::Filter(Filter {
+                    path: Path {
+                        segments: vec![Segment::Key("day".into())]
+                    },
+                    test: Some((Comparison::GreaterOrEqual, Value::Int(2))),
+                })]
+            })
+        );
+    }
+
+    #[test]
+    fn test_display_path() {
+        for path in [
+            "",
+            "days[-1].*[\"a.b\"]",
+            "[\"odd key\"][0]",
+            "*.day",
+            "days[?(@.part1 == null)].part1",
+            "key2[?(@ >= 20)]",
+            "days[?(@[\"a b\"][0])]",
+        ] {
+            let parsed = Path::parse(path).unwrap();
+            assert_eq!(parsed.to_string(), path);
+            assert_eq!(Path::parse(&parsed.to_string()), Ok(parsed));
+        }
+    }
+
+    #[test]
+    fn test_parse_path_errors() {
+        assert_eq!(
+            Path::parse("days..day"),
+            Err(PathError {
+                column: 6,
+                kind: PathErrorKind::UnexpectedChar('.')
+            })
+        );
+        assert_eq!(
+            Path::parse("days[1"),
+            Err(PathError {
+                column: 7,
+                kind: PathErrorKind::UnexpectedEnd
+            })
+        );
+        assert_eq!(
+            Path::parse("[99999999999999999999]").unwrap_err().kind,
+            PathErrorKind::InvalidIndex("99999999999999999999".into())
+        );
+        assert!(matches!(
+            Path::parse("[?(@.day == nope)]").unwrap_err(),
+            PathError {
+                column: 13,
+                kind: PathErrorKind::InvalidValue(_)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_query() {
+        let value = days();
+        assert_eq!(select(&value, ""), vec![&value]);
+        assert_eq!(select(&value, "key2[1]"), vec![&Value::Int(20)]);
+        assert_eq!(select(&value, "key2[-1]"), vec![&Value::Int(30)]);
+        assert!(select(&value, "key2[3]").is_empty());
+        assert_eq!(select(&value, "[\"odd key\"]"), vec![&Value::Bool(true)]);
+        assert_eq!(
+            select(&value, "days.*.part1"),
+            vec![&Value::Int(1132), &Value::Null, &Value::Int(357)]
+        );
+        assert_eq!(
+            select(&value, "days[*].tags[0]"),
+            vec![&Value::Str("dial".into())]
+        );
+        // keys on lists and indexes on maps just don't match anything
+        assert!(select(&value, "days.day").is_empty());
+        assert!(select(&value, "[0]").is_empty());
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let value = days();
+        let day_of = |path: &str| -> Vec<Value> {
+            select(&value, path)
+                .iter()
+                .flat_map(|day| day.select("day").unwrap())
+                .cloned()
+                .collect()
+        };
+        assert_eq!(day_of("days[?(@.name)]"), vec![Value::Int(3)]);
+        assert_eq!(day_of("days[?(@.part1 == null)]"), vec![Value::Int(2)]);
+        assert_eq!(
+            day_of("days[?(@.part1 != null)]"),
+            vec![Value::Int(1), Value::Int(3)]
+        );
+        assert_eq!(day_of("days[?(@.part1 > 1000)]"), vec![Value::Int(1)]);
+        assert_eq!(
+            day_of("days[?(@.day <= 0x2)]"),
+            vec![Value::Int(1), Value::Int(2)]
+        );
+        assert_eq!(day_of("days[?(@.name < \"m\")]"), vec![Value::Int(3)]);
+        assert_eq!(
+            select(&value, "key2[?(@ >= 20)]"),
+            vec![&Value::Int(20), &Value::Int(30)]
+        );
+        assert_eq!(
+            day_of("days[?(@.tags[0] == \"dial\")]"),
+            vec![Value::Int(1)]
+        );
+    }
+
+    #[test]
+    fn test_query_mut() {
+        let mut value = days();
+        let path = Path::parse("days[?(@.part1 == null)].part1").unwrap();
+        for found in value.query_mut(&path) {
+            *found = Value::Int(0);
+        }
+        for found in value.query_mut(&Path::parse("key2.*").unwrap()) {
+            if let Value::Int(int) = found {
+                *int += 1;
+            }
+        }
+        assert_eq!(
+            select(&value, "days.*.part1"),
+            vec![&Value::Int(1132), &Value::Int(0), &Value::Int(357)]
+        );
+        assert_eq!(
+            select(&value, "key2.*"),
+            vec![&Value::Int(11), &Value::Int(21), &Value::Int(31)]
+        );
+    }
+}
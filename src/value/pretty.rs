@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use super::{Value, escape::Quoted};
+
+// how to lay out a value over several lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrettyOptions {
+    // spaces per level of nesting
+    pub indent: usize,
+    // put a comma after the last item of every list and map as well
+    pub trailing_comma: bool,
+    // escape anything outside of ascii in strings and keys
+    pub ascii: bool,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            trailing_comma: false,
+            ascii: false,
+        }
+    }
+}
+
+struct PrettyWriter<'a> {
+    options: &'a PrettyOptions,
+    output: String,
+}
+
+impl PrettyWriter<'_> {
+    fn quoted(&mut self, value: &str) {
+        let quoted = Quoted {
+            value,
+            ascii: self.options.ascii,
+        };
+        write!(self.output, "{}", quoted).expect("Writing to a string can't fail");
+    }
+
+    fn newline(&mut self, depth: usize) {
+        self.output.push('\n');
+        self.output
+            .push_str(&" ".repeat(depth * self.options.indent));
+    }
+
+    // one item per line, each indented a level deeper than the brackets around them
+    fn items<T>(
+        &mut self,
+        (open, close): (char, char),
+        items: impl ExactSizeIterator<Item = T>,
+        depth: usize,
+        mut write_item: impl FnMut(&mut Self, T),
+    ) {
+        self.output.push(open);
+        let len = items.len();
+        if len == 0 {
+            self.output.push(close);
+            return;
+        }
+        for (index, item) in items.enumerate() {
+            self.newline(depth + 1);
+            write_item(self, item);
+            if index + 1 < len || self.options.trailing_comma {
+                self.output.push(',');
+            }
+        }
+        self.newline(depth);
+        self.output.push(close);
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) {
+        match value {
+            Value::Str(string) => self.quoted(string),
+            Value::List(list) => self.items(('[', ']'), list.iter(), depth, |writer, item| {
+                writer.value(item, depth + 1)
+            }),
+            Value::Map(map) => self.items(('{', '}'), map.iter(), depth, |writer, (key, item)| {
+                writer.quoted(key);
+                writer.output.push_str(": ");
+                writer.value(item, depth + 1);
+            }),
+            scalar => write!(self.output, "{}", scalar).expect("Writing to a string can't fail"),
+        }
+    }
+}
+
+impl Value {
+    // the same syntax as Display, spread over lines, which Value::parse also reads back
+    pub fn to_pretty(&self, options: &PrettyOptions) -> String {
+        let mut writer = PrettyWriter {
+            options,
+            output: String::new(),
+        };
+        writer.value(self, 0);
+        writer.output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PrettyOptions;
+    use crate::value::Value;
+
+    fn nested() -> Value {
+        Value::List(vec![
+            Value::Int(1),
+            Value::from_entries(vec![("a\"b", Value::List(vec![Value::Str("é".into())]))]),
+            Value::List(vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_pretty() {
+        assert_eq!(
+            nested().to_pretty(&PrettyOptions::default()),
+            "[\n  1,\n  {\n    \"a\\\"b\": [\n      \"é\"\n    ]\n  },\n  []\n]"
+        );
+        assert_eq!(Value::Int(3).to_pretty(&PrettyOptions::default()), "3");
+    }
+
+    #[test]
+    fn test_pretty_options() {
+        let options = PrettyOptions {
+            indent: 4,
+            trailing_comma: true,
+            ascii: true,
+        };
+        assert_eq!(
+            nested().to_pretty(&options),
+            "[\n    1,\n    {\n        \"a\\\"b\": [\n            \"\\u00e9\",\n        ],\n    },\n    [],\n]"
+        );
+    }
+
+    #[test]
+    fn test_pretty_round_trip() {
+        let options = PrettyOptions {
+            trailing_comma: true,
+            ..PrettyOptions::default()
+        };
+        let radix = Value::List(vec![
+            Value::Hex(255),
+            Value::Bin(2),
+            Value::Exponent(1.5, 2),
+        ]);
+        for options in [options, PrettyOptions::default()] {
+            for value in [nested(), radix.clone()] {
+                let parsed = Value::parse(&value.to_pretty(&options)).unwrap();
+                assert_eq!(parsed.to_string(), value.to_string());
+            }
+        }
+    }
+}
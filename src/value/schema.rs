@@ -0,0 +1,562 @@
+use std::fmt::Display;
+
+use super::{ConvertError, ConvertErrorKind, Map, Path, Segment, Value};
+
+// the shape a value has to have, written as a value like
+// {"type": "map", "fields": {"day": {"type": "int", "min": 1, "max": 25}}}
+// a plain string such as "int" is short for a map with just the type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    Any,
+    Null,
+    Bool,
+    String,
+    // int, hex, octal or binary, bounds are inclusive
+    Int {
+        min: Option<Value>,
+        max: Option<Value>,
+    },
+    // any numeric variant, bounds are inclusive
+    Number {
+        min: Option<Value>,
+        max: Option<Value>,
+    },
+    List {
+        items: Box<Schema>,
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+    },
+    // keys that aren't fields have to match rest, and aren't allowed at all without it
+    Map {
+        fields: Vec<Field>,
+        rest: Option<Box<Schema>>,
+    },
+    AnyOf(Vec<Schema>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub schema: Schema,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    WrongType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    BelowMin {
+        min: Value,
+        found: Value,
+    },
+    AboveMax {
+        max: Value,
+        found: Value,
+    },
+    TooShort {
+        min: usize,
+        length: usize,
+    },
+    TooLong {
+        max: usize,
+        length: usize,
+    },
+    MissingKey(String),
+    UnexpectedKey(String),
+    // every option failed, these are the ways each one did
+    NoMatch(Vec<Vec<Violation>>),
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ViolationKind::WrongType { expected, found } => {
+                write!(f, "Expected {}, found {}", expected, found)
+            }
+            ViolationKind::BelowMin { min, found } => {
+                write!(f, "{} is below the minimum of {}", found, min)
+            }
+            ViolationKind::AboveMax { max, found } => {
+                write!(f, "{} is above the maximum of {}", found, max)
+            }
+            ViolationKind::TooShort { min, length } => {
+                write!(f, "Has {} items, needs at least {}", length, min)
+            }
+            ViolationKind::TooLong { max, length } => {
+                write!(f, "Has {} items, can have at most {}", length, max)
+            }
+            ViolationKind::MissingKey(key) => write!(f, "Missing key '{}'", key),
+            ViolationKind::UnexpectedKey(key) => write!(f, "Unexpected key '{}'", key),
+            ViolationKind::NoMatch(options) => {
+                write!(f, "Doesn't match any of the {} options", options.len())
+            }
+        }
+    }
+}
+
+// one thing wrong with a value, path is where it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub path: Path,
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.segments.is_empty() {
+            write!(f, "At the top: {}", self.kind)
+        } else {
+            write!(f, "At '{}': {}", self.path, self.kind)
+        }
+    }
+}
+
+fn child(path: &Path, segment: Segment) -> Path {
+    let mut path = path.clone();
+    path.segments.push(segment);
+    path
+}
+
+impl Schema {
+    fn name(&self) -> &'static str {
+        match self {
+            Schema::Any => "any",
+            Schema::Null => "null",
+            Schema::Bool => "bool",
+            Schema::String => "string",
+            Schema::Int { .. } => "int",
+            Schema::Number { .. } => "number",
+            Schema::List { .. } => "list",
+            Schema::Map { .. } => "map",
+            Schema::AnyOf(_) => "any_of",
+        }
+    }
+
+    // every violation in the value, not just the first one
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<Violation>> {
+        let mut violations = vec![];
+        self.check(value, &Path::default(), &mut violations);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    fn check(&self, value: &Value, path: &Path, violations: &mut Vec<Violation>) {
+        let mut violation = |kind| {
+            violations.push(Violation {
+                path: path.clone(),
+                kind,
+            })
+        };
+        match (self, value) {
+            (Schema::Any, _)
+            | (Schema::Null, Value::Null)
+            | (Schema::Bool, Value::Bool(_))
+            | (Schema::String, Value::Str(_)) => {}
+            (
+                Schema::Int { min, max },
+                Value::Int(_) | Value::Hex(_) | Value::Oct(_) | Value::Bin(_),
+            ) => check_bounds(min, max, value, violation),
+            (Schema::Number { min, max }, value) if value.as_number().is_some() => {
+                check_bounds(min, max, value, violation)
+            }
+            (
+                Schema::List {
+                    items,
+                    min_length,
+                    max_length,
+                },
+                Value::List(list),
+            ) => {
+                if let Some(min) = min_length.filter(|min| list.len() < *min) {
+                    violation(ViolationKind::TooShort {
+                        min,
+                        length: list.len(),
+                    });
+                }
+                if let Some(max) = max_length.filter(|max| list.len() > *max) {
+                    violation(ViolationKind::TooLong {
+                        max,
+                        length: list.len(),
+                    });
+                }
+                for (index, item) in list.iter().enumerate() {
+                    items.check(item, &child(path, Segment::Index(index as i64)), violations);
+                }
+            }
+            (Schema::Map { fields, rest }, Value::Map(map)) => {
+                check_map(fields, rest, map, path, violations)
+            }
+            (Schema::AnyOf(options), value) => {
+                let mut failures = vec![];
+                for option in options {
+                    match option.validate(value) {
+                        Ok(()) => return,
+                        Err(option_violations) => failures.push(option_violations),
+                    }
+                }
+                violation(ViolationKind::NoMatch(failures));
+            }
+            (schema, value) => violation(ViolationKind::WrongType {
+                expected: schema.name(),
+                found: value.kind_name(),
+            }),
+        }
+    }
+}
+
+fn check_bounds(
+    min: &Option<Value>,
+    max: &Option<Value>,
+    value: &Value,
+    mut violation: impl FnMut(ViolationKind),
+) {
+    if let Some(min) = min.as_ref().filter(|min| value < *min) {
+        violation(ViolationKind::BelowMin {
+            min: min.clone(),
+            found: value.clone(),
+        });
+    }
+    if let Some(max) = max.as_ref().filter(|max| value > *max) {
+        violation(ViolationKind::AboveMax {
+            max: max.clone(),
+            found: value.clone(),
+        });
+    }
+}
+
+fn check_map(
+    fields: &[Field],
+    rest: &Option<Box<Schema>>,
+    map: &Map,
+    path: &Path,
+    violations: &mut Vec<Violation>,
+) {
+    for field in fields {
+        match map.get(&field.name) {
+            Some(value) => field.schema.check(
+                value,
+                &child(path, Segment::Key(field.name.clone())),
+                violations,
+            ),
+            None if field.required => violations.push(Violation {
+                path: path.clone(),
+                kind: ViolationKind::MissingKey(field.name.clone()),
+            }),
+            None => {}
+        }
+    }
+    let others = map
+        .iter()
+        .filter(|(key, _)| !fields.iter().any(|field| &field.name == *key));
+    for (key, value) in others {
+        match rest {
+            Some(rest) => rest.check(value, &child(path, Segment::Key(key.clone())), violations),
+            None => violations.push(Violation {
+                path: path.clone(),
+                kind: ViolationKind::UnexpectedKey(key.clone()),
+            }),
+        }
+    }
+}
+
+// the keys a schema map can have for its type, anything else is a typo
+fn allowed_keys(name: &str) -> &'static [&'static str] {
+    match name {
+        "int" | "number" => &["type", "required", "min", "max"],
+        "list" => &["type", "required", "items", "min_length", "max_length"],
+        "map" => &["type", "required", "fields", "rest"],
+        "any_of" => &["type", "required", "options"],
+        _ => &["type", "required"],
+    }
+}
+
+fn bound(value: &Value, key: &str) -> Result<Option<Value>, ConvertError> {
+    let found = match value {
+        Value::Map(map) => map.get(key),
+        _ => None,
+    };
+    match found {
+        None | Some(Value::Null) => Ok(None),
+        Some(bound) if bound.as_number().is_some() => Ok(Some(bound.clone())),
+        Some(bound) => Err(ConvertError::wrong_type("number", bound).within_key(key)),
+    }
+}
+
+fn nested(value: &Value, key: &str) -> Result<Option<Box<Schema>>, ConvertError> {
+    Ok(value.field::<Option<Schema>>(key)?.map(Box::new))
+}
+
+impl TryFrom<&Value> for Schema {
+    type Error = ConvertError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let name = match value {
+            Value::Str(name) => name.clone(),
+            Value::Map(map) => {
+                let name: String = value.field("type")?;
+                let allowed = allowed_keys(&name);
+                if let Some(key) = map.keys().find(|key| !allowed.contains(&key.as_str())) {
+                    return Err(ConvertError::new(ConvertErrorKind::UnknownName(
+                        key.clone(),
+                    )));
+                }
+                name
+            }
+            other => return Err(ConvertError::wrong_type("map", other)),
+        };
+        // a bare string has none of the optional keys, so reading them from null gives defaults
+        let options = match value {
+            Value::Map(_) => value,
+            _ => &Value::from_entries(vec![]),
+        };
+        Ok(match name.as_str() {
+            "any" => Schema::Any,
+            "null" => Schema::Null,
+            "bool" => Schema::Bool,
+            "string" => Schema::String,
+            "int" => Schema::Int {
+                min: bound(options, "min")?,
+                max: bound(options, "max")?,
+            },
+            "number" => Schema::Number {
+                min: bound(options, "min")?,
+                max: bound(options, "max")?,
+            },
+            "list" => Schema::List {
+                items: nested(options, "items")?.unwrap_or(Box::new(Schema::Any)),
+                min_length: options.field("min_length")?,
+                max_length: options.field("max_length")?,
+            },
+            "map" => {
+                let fields = match options.field::<Option<Value>>("fields")? {
+                    Some(Value::Map(fields)) => fields
+                        .iter()
+                        .map(|(name, field)| {
+                            let schema = Schema::try_from(field)
+                                .map_err(|error| error.within_key(name).within_key("fields"))?;
+                            let required = match field {
+                                Value::Map(_) => field
+                                    .field::<Option<bool>>("required")
+                                    .map_err(|error| error.within_key(name).within_key("fields"))?
+                                    .unwrap_or(true),
+                                _ => true,
+                            };
+                            Ok(Field {
+                                name: name.clone(),
+                                schema,
+                                required,
+                            })
+                        })
+                        .collect::<Result<_, ConvertError>>()?,
+                    Some(other) => {
+                        return Err(ConvertError::wrong_type("map", &other).within_key("fields"));
+                    }
+                    None => vec![],
+                };
+                Schema::Map {
+                    fields,
+                    rest: nested(options, "rest")?,
+                }
+            }
+            "any_of" => Schema::AnyOf(
+                options
+                    .field::<Option<Vec<Schema>>>("options")?
+                    .unwrap_or_default(),
+            ),
+            _ => {
+                return Err(
+                    ConvertError::new(ConvertErrorKind::UnknownName(name)).within_key("type")
+                );
+            }
+        })
+    }
+}
+
+impl From<&Schema> for Value {
+    fn from(value: &Schema) -> Self {
+        let mut map = Map::new();
+        map.insert("type", Value::Str(value.name().into()));
+        let mut optional = |key: &str, entry: Option<Value>| {
+            if let Some(entry) = entry {
+                map.insert(key, entry);
+            }
+        };
+        match value {
+            Schema::Int { min, max } | Schema::Number { min, max } => {
+                optional("min", min.clone());
+                optional("max", max.clone());
+            }
+            Schema::List {
+                items,
+                min_length,
+                max_length,
+            } => {
+                optional("items", Some(Value::from(items.as_ref())));
+                optional("min_length", min_length.map(Value::from));
+                optional("max_length", max_length.map(Value::from));
+            }
+            Schema::Map { fields, rest } => {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        let mut schema = Value::from(&field.schema);
+                        if let (false, Value::Map(schema)) = (field.required, &mut schema) {
+                            schema.insert("required", Value::Bool(false));
+                        }
+                        (field.name.clone(), schema)
+                    })
+                    .collect::<Map>();
+                optional("fields", Some(Value::Map(fields)));
+                optional("rest", rest.as_deref().map(Value::from));
+            }
+            Schema::AnyOf(options) => {
+                optional(
+                    "options",
+                    Some(Value::List(options.iter().map(Value::from).collect())),
+                );
+            }
+            Schema::Any | Schema::Null | Schema::Bool | Schema::String => {}
+        }
+        Value::Map(map)
+    }
+}
+
+impl Value {
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<Violation>> {
+        schema.validate(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Schema, Violation, ViolationKind};
+    use crate::value::{ConvertErrorKind, Path, Value};
+
+    const SCHEMA: &str = r#"{
+        "type": "map",
+        "fields": {
+            "name": "string",
+            "jobs": {"type": "int", "min": 1, "max": 0x40, "required": false},
+            "days": {
+                "type": "list",
+                "min_length": 1,
+                "max_length": 3,
+                "items": {
+                    "type": "map",
+                    "fields": {
+                        "day": {"type": "int", "min": 1, "max": 12},
+                        "expected": {"type": "any_of", "options": ["int", "null"]}
+                    }
+                }
+            },
+            "threshold": {"type": "number", "min": 0, "required": false}
+        },
+        "rest": "bool"
+    }"#;
+
+    fn schema() -> Schema {
+        Schema::try_from(&Value::parse(SCHEMA).unwrap()).unwrap()
+    }
+
+    fn violations(input: &str) -> Vec<String> {
+        match schema().validate(&Value::parse(input).unwrap()) {
+            Ok(()) => vec![],
+            Err(violations) => violations.iter().map(Violation::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_valid() {
+        assert!(
+            violations(
+                r#"{"name": "aoc", "days": [{"day": 1, "expected": 1132}, {"day": 0xC, "expected": null}]}"#
+            )
+            .is_empty()
+        );
+        assert!(
+            violations(r#"{"name": "aoc", "jobs": 4, "threshold": 2.5e1, "days": [{"day": 2, "expected": 1}], "verbose": true}"#)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_every_violation() {
+        assert_eq!(
+            violations(
+                r#"{
+                    "jobs": 0,
+                    "days": [{"day": 13, "expected": "1132"}, {"day": 1.5}, [], 4],
+                    "threshold": -Infinity,
+                    "verbose": "yes"
+                }"#
+            ),
+            vec![
+                "At the top: Missing key 'name'",
+                "At 'jobs': 0 is below the minimum of 1",
+                "At 'days': Has 4 items, can have at most 3",
+                "At 'days[0].day': 13 is above the maximum of 12",
+                "At 'days[0].expected': Doesn't match any of the 2 options",
+                "At 'days[1].day': Expected int, found float",
+                "At 'days[1]': Missing key 'expected'",
+                "At 'days[2]': Expected map, found list",
+                "At 'days[3]': Expected map, found int",
+                "At 'threshold': -Infinity is below the minimum of 0",
+                "At 'verbose': Expected bool, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_closed_map() {
+        let schema = Schema::try_from(&Value::parse(r#"{"type": "map"}"#).unwrap()).unwrap();
+        assert_eq!(
+            schema.validate(&Value::parse(r#"{"a": 1}"#).unwrap()),
+            Err(vec![Violation {
+                path: Path::default(),
+                kind: ViolationKind::UnexpectedKey("a".into())
+            }])
+        );
+        assert_eq!(Value::parse("[]").unwrap().validate(&Schema::Any), Ok(()));
+    }
+
+    #[test]
+    fn test_schema_round_trip() {
+        let schema = schema();
+        let value = Value::from(&schema);
+        assert_eq!(Schema::try_from(&value), Ok(schema));
+        assert_eq!(
+            Value::from(&Schema::try_from(&Value::Str("list".into())).unwrap()).to_string(),
+            r#"{"type": "list", "items": {"type": "any"}}"#
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let error = |input: &str| Schema::try_from(&Value::parse(input).unwrap()).unwrap_err();
+        let unknown =
+            error(r#"{"type": "map", "fields": {"a": {"type": "list", "items": "integer"}}}"#);
+        assert_eq!(unknown.path, "fields.a.items.type");
+        assert_eq!(
+            unknown.kind,
+            ConvertErrorKind::UnknownName("integer".into())
+        );
+        assert_eq!(
+            error(r#"{"type": "int", "minimum": 1}"#).kind,
+            ConvertErrorKind::UnknownName("minimum".into())
+        );
+        assert_eq!(error(r#"{"type": "int", "min": "1"}"#).path, "min");
+        assert_eq!(
+            error(r#"{"type": "list", "max_length": -1}"#).to_string(),
+            "At 'max_length': -1 doesn't fit in usize"
+        );
+        assert_eq!(
+            error(r#"{"min": 1}"#).kind,
+            ConvertErrorKind::MissingKey("type".into())
+        );
+    }
+}
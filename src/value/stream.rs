@@ -0,0 +1,293 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use super::{Json, Value, escape::Quoted};
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    // end_list with something else open, or nothing at all
+    NotInList,
+    NotInMap,
+    // a value inside a map without a key before it
+    ExpectedKey,
+    // a key outside a map, or two keys in a row
+    UnexpectedKey,
+    // anything written once the top level value is complete
+    AlreadyComplete,
+    // finished with lists or maps still open, or nothing written
+    Incomplete { open: usize },
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(error) => write!(f, "Couldn't write value: {}", error),
+            StreamError::NotInList => write!(f, "No list to end"),
+            StreamError::NotInMap => write!(f, "No map to end"),
+            StreamError::ExpectedKey => write!(f, "Map values need a key first"),
+            StreamError::UnexpectedKey => write!(f, "Keys can only go in a map before a value"),
+            StreamError::AlreadyComplete => write!(f, "The value is already complete"),
+            StreamError::Incomplete { open: 0 } => write!(f, "Nothing was written"),
+            StreamError::Incomplete { open } => write!(f, "{} lists or maps still open", open),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(value: io::Error) -> Self {
+        StreamError::Io(value)
+    }
+}
+
+enum Frame {
+    List { count: usize },
+    Map { count: usize, has_key: bool },
+}
+
+// writes one value a piece at a time, so huge lists never have to exist in memory
+// writer.begin_list()?; for id in ids { writer.push(&Value::Int(id))? } writer.end_list()?;
+pub struct ValueWriter<W: Write> {
+    output: W,
+    json: bool,
+    stack: Vec<Frame>,
+    complete: bool,
+}
+
+impl<W: Write> ValueWriter<W> {
+    // the same text as displaying the finished value would give
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            json: false,
+            stack: vec![],
+            complete: false,
+        }
+    }
+
+    // the same text as to_json on the finished value would give
+    pub fn json(output: W) -> Self {
+        Self {
+            json: true,
+            ..Self::new(output)
+        }
+    }
+
+    fn separator(&self) -> &'static str {
+        if self.json { "," } else { ", " }
+    }
+
+    // writes whatever has to come between the last thing and the next value
+    fn start_value(&mut self) -> Result<(), StreamError> {
+        let separator = self.separator();
+        match self.stack.last_mut() {
+            None if self.complete => Err(StreamError::AlreadyComplete),
+            None => Ok(()),
+            Some(Frame::List { count }) => {
+                if *count > 0 {
+                    self.output.write_all(separator.as_bytes())?;
+                }
+                *count += 1;
+                Ok(())
+            }
+            Some(Frame::Map { has_key, .. }) if !*has_key => Err(StreamError::ExpectedKey),
+            Some(Frame::Map { has_key, .. }) => {
+                *has_key = false;
+                Ok(())
+            }
+        }
+    }
+
+    fn end_value(&mut self) {
+        if self.stack.is_empty() {
+            self.complete = true;
+        }
+    }
+
+    pub fn push(&mut self, value: &Value) -> Result<(), StreamError> {
+        self.start_value()?;
+        if self.json {
+            write!(self.output, "{}", Json(value))?;
+        } else {
+            write!(self.output, "{}", value)?;
+        }
+        self.end_value();
+        Ok(())
+    }
+
+    pub fn key(&mut self, key: &str) -> Result<(), StreamError> {
+        let separator = self.separator();
+        let Some(Frame::Map { count, has_key }) = self.stack.last_mut() else {
+            return Err(StreamError::UnexpectedKey);
+        };
+        if *has_key {
+            return Err(StreamError::UnexpectedKey);
+        }
+        if *count > 0 {
+            self.output.write_all(separator.as_bytes())?;
+        }
+        *count += 1;
+        *has_key = true;
+        let colon = if self.json { ":" } else { ": " };
+        write!(self.output, "{}{}", Quoted::new(key), colon)?;
+        Ok(())
+    }
+
+    // a key and its value in one go
+    pub fn entry(&mut self, key: &str, value: &Value) -> Result<(), StreamError> {
+        self.key(key)?;
+        self.push(value)
+    }
+
+    pub fn begin_list(&mut self) -> Result<(), StreamError> {
+        self.start_value()?;
+        self.output.write_all(b"[")?;
+        self.stack.push(Frame::List { count: 0 });
+        Ok(())
+    }
+
+    pub fn end_list(&mut self) -> Result<(), StreamError> {
+        let Some(Frame::List { .. }) = self.stack.last() else {
+            return Err(StreamError::NotInList);
+        };
+        self.stack.pop();
+        self.output.write_all(b"]")?;
+        self.end_value();
+        Ok(())
+    }
+
+    pub fn begin_map(&mut self) -> Result<(), StreamError> {
+        self.start_value()?;
+        self.output.write_all(b"{")?;
+        self.stack.push(Frame::Map {
+            count: 0,
+            has_key: false,
+        });
+        Ok(())
+    }
+
+    // a key without a value after it can't be ended
+    pub fn end_map(&mut self) -> Result<(), StreamError> {
+        match self.stack.last() {
+            Some(Frame::Map { has_key: false, .. }) => {}
+            Some(Frame::Map { has_key: true, .. }) => return Err(StreamError::ExpectedKey),
+            _ => return Err(StreamError::NotInMap),
+        }
+        self.stack.pop();
+        self.output.write_all(b"}")?;
+        self.end_value();
+        Ok(())
+    }
+
+    // checks the value is complete and hands back the output, flushed
+    pub fn finish(mut self) -> Result<W, StreamError> {
+        if !self.complete {
+            return Err(StreamError::Incomplete {
+                open: self.stack.len(),
+            });
+        }
+        self.output.flush()?;
+        Ok(self.output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use super::{StreamError, ValueWriter};
+    use crate::value::Value;
+
+    fn text(writer: ValueWriter<Vec<u8>>) -> String {
+        String::from_utf8(writer.finish().unwrap()).unwrap()
+    }
+
+    fn days(writer: &mut ValueWriter<Vec<u8>>) -> Result<(), StreamError> {
+        writer.begin_map()?;
+        writer.entry("name", &Value::Str("aoc".into()))?;
+        writer.key("days")?;
+        writer.begin_list()?;
+        for day in 1..=2 {
+            writer.begin_map()?;
+            writer.entry("day", &Value::Int(day))?;
+            writer.key("parts")?;
+            writer.begin_list()?;
+            writer.end_list()?;
+            writer.end_map()?;
+        }
+        writer.push(&Value::Hex(255))?;
+        writer.end_list()?;
+        writer.end_map()
+    }
+
+    #[test]
+    fn test_matches_display() {
+        let expected = Value::parse(
+            r#"{"name": "aoc", "days": [{"day": 1, "parts": []}, {"day": 2, "parts": []}, 0xFF]}"#,
+        )
+        .unwrap();
+
+        let mut writer = ValueWriter::new(vec![]);
+        days(&mut writer).unwrap();
+        assert_eq!(text(writer), expected.to_string());
+
+        let mut writer = ValueWriter::json(vec![]);
+        days(&mut writer).unwrap();
+        assert_eq!(text(writer), expected.to_json());
+    }
+
+    #[test]
+    fn test_scalar() {
+        let mut writer = ValueWriter::new(vec![]);
+        writer.push(&Value::Str("hi".into())).unwrap();
+        assert!(matches!(
+            writer.push(&Value::Null),
+            Err(StreamError::AlreadyComplete)
+        ));
+        assert_eq!(text(writer), "\"hi\"");
+    }
+
+    #[test]
+    fn test_misuse() {
+        let mut writer = ValueWriter::new(vec![]);
+        assert!(matches!(writer.end_list(), Err(StreamError::NotInList)));
+        assert!(matches!(writer.key("a"), Err(StreamError::UnexpectedKey)));
+        writer.begin_map().unwrap();
+        assert!(matches!(
+            writer.push(&Value::Null),
+            Err(StreamError::ExpectedKey)
+        ));
+        assert!(matches!(writer.end_list(), Err(StreamError::NotInList)));
+        writer.key("a").unwrap();
+        assert!(matches!(writer.key("b"), Err(StreamError::UnexpectedKey)));
+        assert!(matches!(writer.end_map(), Err(StreamError::ExpectedKey)));
+        writer.begin_list().unwrap();
+        assert!(matches!(
+            writer.finish(),
+            Err(StreamError::Incomplete { open: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_large_list() {
+        let mut writer = ValueWriter::new(io::sink());
+        writer.begin_list().unwrap();
+        for id in 0..2_000_000 {
+            writer.push(&Value::Int(id)).unwrap();
+        }
+        writer.end_list().unwrap();
+        writer.finish().unwrap();
+
+        let mut writer = ValueWriter::new(vec![]);
+        writer.begin_list().unwrap();
+        for id in 0..100_000 {
+            writer.push(&Value::Int(id)).unwrap();
+        }
+        writer.end_list().unwrap();
+        let output = text(writer);
+        assert!(output.starts_with("[0, 1, 2, "));
+        assert!(output.ends_with(", 99999]"));
+    }
+}
@@ -0,0 +1,346 @@
+use std::fmt::Display;
+
+use crate::{
+    input::InputSource,
+    parsing::{ParsePolicy, ParseWarning},
+    runner,
+    solution::{Answer, DynSolution, Part, SolutionError},
+    value::Value,
+};
+
+// the checked in known answers, relative to the crate root
+pub const ANSWERS_FILE_PATH: &str = "./answers.txt";
+
+// which input an answer was recorded against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnswerInput {
+    // the solution's own worked example
+    Example,
+    // the real puzzle input, wherever the default input source finds it
+    Input,
+}
+
+impl AnswerInput {
+    pub const ALL: [AnswerInput; 2] = [AnswerInput::Example, AnswerInput::Input];
+}
+
+impl Display for AnswerInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerInput::Example => write!(f, "example"),
+            AnswerInput::Input => write!(f, "input"),
+        }
+    }
+}
+
+impl TryFrom<&str> for AnswerInput {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "example" => Ok(AnswerInput::Example),
+            "input" => Ok(AnswerInput::Input),
+            other => Err(format!(
+                "Unknown input '{}', expected example or input",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownAnswer {
+    pub day: u8,
+    pub part: Part,
+    pub input: AnswerInput,
+    pub answer: Answer,
+}
+
+fn parse_known_answer(line: &str) -> Result<KnownAnswer, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [day, part, input, answer] = fields[..] else {
+        return Err(format!(
+            "Expected '<day> <part> <input> <answer>', got '{}'",
+            line
+        ));
+    };
+    Ok(KnownAnswer {
+        day: day
+            .parse()
+            .map_err(|error| format!("Invalid day '{}': {}", day, error))?,
+        part: Part::try_from(part).map_err(|part| format!("Invalid part '{}'", part))?,
+        input: AnswerInput::try_from(input)?,
+        answer: answer
+            .parse()
+            .map_err(|error| format!("Invalid answer '{}': {}", answer, error))?,
+    })
+}
+
+// one answer per line, blank lines and lines starting with # are ignored
+pub fn parse_answers(input: &str) -> Result<Vec<KnownAnswer>, ParseWarning> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            parse_known_answer(line).map_err(|error| ParseWarning::new(index + 1, error))
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Result<Answer, String>,
+    },
+    // nothing recorded yet, with whatever the solution currently gives
+    MissingAnswer {
+        actual: Result<Answer, String>,
+    },
+    // an answer is recorded but the input isn't available to check it
+    MissingInput {
+        reason: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part: Part,
+    pub input: AnswerInput,
+    pub status: Status,
+}
+
+fn actual_entries(actual: &Result<Answer, String>) -> (&'static str, Value) {
+    match actual {
+        Ok(answer) => ("actual", Value::Int(*answer)),
+        Err(error) => ("error", Value::Str(error.clone())),
+    }
+}
+
+impl From<&Verification> for Value {
+    fn from(value: &Verification) -> Self {
+        let mut entries = vec![
+            ("day", Value::Int(value.day as i64)),
+            ("part", Value::from(value.part)),
+            ("input", Value::Str(value.input.to_string())),
+        ];
+        match &value.status {
+            Status::Pass => entries.push(("status", Value::Str("pass".into()))),
+            Status::Fail { expected, actual } => {
+                entries.push(("status", Value::Str("fail".into())));
+                entries.push(("expected", Value::Int(*expected)));
+                entries.push(actual_entries(actual));
+            }
+            Status::MissingAnswer { actual } => {
+                entries.push(("status", Value::Str("missing".into())));
+                entries.push(actual_entries(actual));
+            }
+            Status::MissingInput { reason } => {
+                entries.push(("status", Value::Str("missing".into())));
+                entries.push(("error", Value::Str(reason.clone())));
+            }
+        }
+        Value::from_entries(entries)
+    }
+}
+
+fn source_for(solution: &dyn DynSolution, input: AnswerInput, real: &InputSource) -> InputSource {
+    match input {
+        AnswerInput::Example => InputSource::Memory(solution.example().into()),
+        AnswerInput::Input => real.clone(),
+    }
+}
+
+fn verify_input(
+    solution: &dyn DynSolution,
+    input: AnswerInput,
+    source: &InputSource,
+    answers: &[KnownAnswer],
+) -> Vec<Verification> {
+    let day = solution.day();
+    let expected_for = |part: Part| {
+        answers
+            .iter()
+            .find(|known| known.day == day && known.part == part && known.input == input)
+            .map(|known| known.answer)
+    };
+    let verification = |part: Part, status: Status| Verification {
+        day,
+        part,
+        input,
+        status,
+    };
+
+    // verifying is about catching regressions, so anything the parser skips counts
+    let result = match runner::run(solution, source, ParsePolicy::Strict, &Part::ALL) {
+        Ok(result) => result,
+        Err(SolutionError::Input(error)) => {
+            // no input just means nothing can be checked, only report what was expected
+            return Part::ALL
+                .iter()
+                .filter(|part| expected_for(**part).is_some())
+                .map(|part| {
+                    verification(
+                        *part,
+                        Status::MissingInput {
+                            reason: error.to_string(),
+                        },
+                    )
+                })
+                .collect();
+        }
+        Err(error) => {
+            return Part::ALL
+                .iter()
+                .map(|part| {
+                    let actual = Err(error.to_string());
+                    let status = match expected_for(*part) {
+                        Some(expected) => Status::Fail { expected, actual },
+                        None => Status::MissingAnswer { actual },
+                    };
+                    verification(*part, status)
+                })
+                .collect();
+        }
+    };
+
+    result
+        .parts
+        .into_iter()
+        .filter_map(|result| {
+            let status = match (expected_for(result.part), result.answer) {
+                (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+                (Some(expected), actual) => Status::Fail {
+                    expected,
+                    actual: actual.map_err(|error| error.to_string()),
+                },
+                // unsolved parts aren't expected to have an answer yet
+                (None, Err(SolutionError::Unsolved { .. })) => return None,
+                (None, actual) => Status::MissingAnswer {
+                    actual: actual.map_err(|error| error.to_string()),
+                },
+            };
+            Some(verification(result.part, status))
+        })
+        .collect()
+}
+
+// checks every solution against every answer, and reports anything without one
+pub fn verify(
+    solutions: &[&dyn DynSolution],
+    answers: &[KnownAnswer],
+    real: &InputSource,
+) -> Vec<Verification> {
+    let mut verifications: Vec<Verification> = solutions
+        .iter()
+        .flat_map(|solution| {
+            AnswerInput::ALL.iter().flat_map(|input| {
+                let source = source_for(*solution, *input, real);
+                verify_input(*solution, *input, &source, answers)
+            })
+        })
+        .collect();
+
+    // answers for days nobody has solved are most likely a typo
+    verifications.extend(
+        answers
+            .iter()
+            .filter(|known| !solutions.iter().any(|solution| solution.day() == known.day))
+            .map(|known| Verification {
+                day: known.day,
+                part: known.part,
+                input: known.input,
+                status: Status::Fail {
+                    expected: known.answer,
+                    actual: Err(format!("There is no solution for day {}", known.day)),
+                },
+            }),
+    );
+    verifications
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ANSWERS_FILE_PATH, AnswerInput, KnownAnswer, Status, parse_answers, verify};
+    use crate::{input::InputSource, registry, solution::Part};
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# day part input answer\n\n1 1 example 3\n6 2 input 42\n")
+            .expect("Should parse correctly");
+        assert_eq!(
+            answers,
+            vec![
+                KnownAnswer {
+                    day: 1,
+                    part: Part::One,
+                    input: AnswerInput::Example,
+                    answer: 3
+                },
+                KnownAnswer {
+                    day: 6,
+                    part: Part::Two,
+                    input: AnswerInput::Input,
+                    answer: 42
+                },
+            ]
+        );
+        let error = parse_answers("1 1 example 3\n1 3 example 3").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(parse_answers("1 1 sample 3").is_err());
+        assert!(parse_answers("1 1 example").is_err());
+    }
+
+    #[test]
+    fn test_verify_statuses() {
+        let answers = parse_answers("1 1 example 3\n1 2 example 7\n26 1 example 1").unwrap();
+        let day_1 = registry::find(1).unwrap();
+        let missing = InputSource::from("./does/not/exist.txt");
+        let verifications = verify(&[day_1], &answers, &missing);
+        let statuses: Vec<(u8, Part, AnswerInput, &Status)> = verifications
+            .iter()
+            .map(|v| (v.day, v.part, v.input, &v.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, Part::One, AnswerInput::Example, &Status::Pass),
+                (
+                    1,
+                    Part::Two,
+                    AnswerInput::Example,
+                    &Status::Fail {
+                        expected: 7,
+                        actual: Ok(6)
+                    }
+                ),
+                (
+                    26,
+                    Part::One,
+                    AnswerInput::Example,
+                    &Status::Fail {
+                        expected: 1,
+                        actual: Err("There is no solution for day 26".into())
+                    }
+                ),
+            ]
+        );
+    }
+
+    // the real regression check: nothing recorded in answers.txt may be wrong
+    #[test]
+    fn test_known_answers() {
+        let answers = std::fs::read_to_string(ANSWERS_FILE_PATH).expect("Should be checked in");
+        let answers = parse_answers(&answers).expect("Should parse correctly");
+        let verifications = verify(registry::SOLUTIONS, &answers, &InputSource::Default);
+        let failures: Vec<String> = verifications
+            .iter()
+            .filter(|v| matches!(v.status, Status::Fail { .. }))
+            .map(|v| format!("day {} part {} {}: {:?}", v.day, v.part, v.input, v.status))
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}